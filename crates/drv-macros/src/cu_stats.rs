@@ -1,34 +1,123 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use syn::{parse_quote, ItemFn, ReturnType, Stmt};
+use syn::{parse2, ItemFn, LitStr};
 
-pub(crate) fn cu_stats_inner(mut input_fn: ItemFn) -> TokenStream {
-    let name = input_fn.sig.ident.to_string();
+use crate::errors::MacroError;
 
-    let log_before = quote! {
-            solana_program::msg!("{}: CU before ", #name);
-            solana_program::log::sol_log_compute_units();
+/// Log prefix of a scope entry, followed by the scope label
+const ENTER_PREFIX: &str = "cu:>";
+/// Log prefix of a scope exit, followed by the scope label
+const EXIT_PREFIX: &str = "cu:<";
 
+/// Wraps function body into a scope guard
+///
+/// Entry logs the label and remaining compute units, the guard logs remaining
+/// compute units and the label when dropped, so every exit path including
+/// early returns and `?` is measured. Instrumentation is compiled only for
+/// the `solana` target.
+pub(crate) fn cu_stats_inner(
+    attrs: TokenStream,
+    mut input_fn: ItemFn,
+) -> Result<TokenStream, MacroError> {
+    let label = if attrs.is_empty() {
+        input_fn.sig.ident.to_string()
+    } else {
+        parse2::<LitStr>(attrs)
+            .map_err(|err| {
+                MacroError::IncorrectMacroUsage(
+                    "expected optional string literal label".to_string(),
+                    err.span(),
+                )
+            })?
+            .value()
     };
 
-    let log_after = quote! {
-            solana_program::log::sol_log_compute_units();
-            solana_program::msg!("{}: CU after", #name);
-    };
+    let enter = format!("{}{}", ENTER_PREFIX, label);
+    let exit = format!("{}{}", EXIT_PREFIX, label);
+    let block = &input_fn.block;
 
-    let log_before_stmt: Stmt = parse_quote!({#log_before});
-    let log_after_stmt: Stmt = parse_quote!({#log_after});
-
-    input_fn.block.stmts.insert(0, log_before_stmt);
-    let pos = {
-        let len = input_fn.block.stmts.len();
-        if let ReturnType::Default = input_fn.sig.output {
-            len
-        } else {
-            len - 2
-        }
-    };
-    input_fn.block.stmts.insert(pos, log_after_stmt);
-    quote! {#input_fn}
+    *input_fn.block = syn::parse_quote!({
+        #[cfg(target_os = "solana")]
+        let __cu_stats_scope = {
+            struct CuStatsScope;
+
+            impl Drop for CuStatsScope {
+                fn drop(&mut self) {
+                    unsafe { ::solana_msg::syscalls::sol_log_compute_units_() };
+                    ::solana_msg::sol_log(#exit);
+                }
+            }
+
+            ::solana_msg::sol_log(#enter);
+            unsafe { ::solana_msg::syscalls::sol_log_compute_units_() };
+            CuStatsScope
+        };
+
+        #block
+    });
+
+    Ok(quote! {#input_fn})
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+    use syn::{parse_quote, Expr, ItemFn, Stmt};
+
+    use super::*;
+
+    #[test]
+    fn test_prefixes() {
+        assert_eq!(ENTER_PREFIX, drv_models::cu_stats::CU_ENTER_PREFIX);
+        assert_eq!(EXIT_PREFIX, drv_models::cu_stats::CU_EXIT_PREFIX);
+    }
+
+    #[test]
+    fn test_expansion() {
+        let input_fn: ItemFn = parse_quote! {
+            fn process(value: u32) -> Result<u32, ProgramError> {
+                if value == 0 {
+                    return Ok(0);
+                }
+                let checked = check(value)?;
+                Ok(checked)
+            }
+        };
+        let block = input_fn.block.clone();
+
+        let output = cu_stats_inner(quote!("process::check"), input_fn).expect("expanded");
+        let output_fn: ItemFn = parse2(output).expect("function");
+        let [Stmt::Local(scope), Stmt::Expr(Expr::Block(body), None)] =
+            output_fn.block.stmts.as_slice()
+        else {
+            panic!("expected scope guard followed by the original body");
+        };
+
+        // Guard lives in the outermost block, so it is dropped on the early
+        // return and on `?` as well as on the tail expression
+        assert_eq!(scope.pat.to_token_stream().to_string(), "__cu_stats_scope");
+        assert_eq!(
+            body.block.to_token_stream().to_string(),
+            block.to_token_stream().to_string()
+        );
+
+        let scope = scope.to_token_stream().to_string();
+        assert!(scope.contains("\"cu:>process::check\""));
+        assert!(scope.contains("\"cu:<process::check\""));
+        assert!(scope.contains("impl Drop for CuStatsScope"));
+    }
+
+    #[test]
+    fn test_default_label() {
+        let input_fn: ItemFn = parse_quote! {
+            fn settle() {}
+        };
+
+        let output = cu_stats_inner(TokenStream::new(), input_fn)
+            .expect("expanded")
+            .to_string();
+        assert!(output.contains("\"cu:>settle\""));
+        assert!(output.contains("\"cu:<settle\""));
+    }
 }
@@ -2,7 +2,10 @@ use std::collections::HashSet;
 
 use proc_macro2::TokenStream;
 
-use crate::errors::MacroError;
+use crate::{
+    drv_layout::{layout_items, LayoutArgs},
+    errors::MacroError,
+};
 use quote::{format_ident, quote};
 use syn::{
     punctuated::Punctuated, spanned::Spanned, token::Comma, Attribute, Data, DeriveInput, Field,
@@ -153,6 +156,7 @@ fn impl_derefs(name: &Ident, inner_name: &Ident, generics: &Generics) -> TokenSt
 pub(crate) fn drv_account_inner(
     input: &DeriveInput,
     attrs: Vec<&Attribute>,
+    layout_args: &LayoutArgs,
 ) -> Result<TokenStream, MacroError> {
     let name = &input.ident;
     let vis = &input.vis;
@@ -191,15 +195,22 @@ pub(crate) fn drv_account_inner(
 
     let derefs = impl_derefs(name, &shadow_name, generics);
 
+    let layout = layout_items(&shadow_name, fields, layout_args);
+
     let (impl_generics, ty_generics, _) = generics.split_for_impl();
 
     Ok(quote! {
         #account_structs
 
         impl #impl_generics #name #ty_generics  {
+            /// `(field, offset)` of every field in declaration order
+            pub const FIELD_OFFSETS: &'static [(&'static str, usize)] = #shadow_name::FIELD_OFFSETS;
+
             #new_method
         }
 
         #derefs
+
+        #layout
     })
 }
@@ -0,0 +1,75 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Path};
+
+use crate::{
+    drv_log::{tagged_fields, TaggedFields},
+    errors::MacroError,
+};
+
+/// Marker path from `#[drv_instruction(Marker)]`
+fn parse_marker(input: &DeriveInput) -> Result<Path, MacroError> {
+    input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("drv_instruction"))
+        .ok_or_else(|| {
+            MacroError::IncorrectMacroUsage(
+                "expected `#[drv_instruction(Marker)]`".to_string(),
+                input.ident.span(),
+            )
+        })?
+        .parse_args::<Path>()
+        .map_err(|err| MacroError::IncorrectMacroUsage(err.to_string(), err.span()))
+}
+
+/// Binds instruction payload to its `DrvInstruction` marker
+///
+/// Generates `TAG`, constructor filling the tag and zeroing padding and
+/// checked `parse`, leading `tag: u8` is asserted at compile time
+pub(crate) fn drv_instruction_inner(input: &DeriveInput) -> Result<TokenStream, MacroError> {
+    let name = &input.ident;
+    let TaggedFields { values, padding } = tagged_fields(input)?;
+    let marker = parse_marker(input)?;
+
+    let value_names = values.iter().map(|(ident, _)| ident).collect::<Vec<_>>();
+    let value_types = values.iter().map(|(_, ty)| ty);
+
+    let offset_msg = format!("{} must start with `tag: u8`", name);
+
+    Ok(quote! {
+        const _: fn(&#name) -> u8 = |data| data.tag;
+        const _: () = assert!(::core::mem::offset_of!(#name, tag) == 0, #offset_msg);
+
+        impl #name {
+            /// `INSTRUCTION_NUMBER` of the instruction using the payload
+            pub const TAG: u8 =
+                <#marker as crate::instruction_constants::DrvInstruction>::INSTRUCTION_NUMBER;
+
+            /// Payload with `TAG` and zeroed padding
+            #[allow(clippy::too_many_arguments)]
+            pub const fn new(#(#value_names: #value_types),*) -> Self {
+                Self {
+                    tag: Self::TAG,
+                    #(#value_names,)*
+                    #(#padding: 0,)*
+                }
+            }
+
+            /// Reads the payload from the beginning of instruction data,
+            /// trailing bytes are left to the caller
+            ///
+            /// ### Errors
+            /// - `InvalidDataLength` - data is shorter than the payload
+            /// - `InvalidClientDataFormat` - tag differs from `TAG`
+            pub fn parse(data: &[u8]) -> Result<Self, crate::errors::ModelsError> {
+                let payload: Self = crate::instruction::read_payload(data)?;
+                if payload.tag != Self::TAG {
+                    return Err(crate::errors::ModelsError::InvalidClientDataFormat);
+                }
+
+                Ok(payload)
+            }
+        }
+    })
+}
@@ -0,0 +1,101 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    meta::ParseNestedMeta, punctuated::Punctuated, spanned::Spanned, token::Comma, Data,
+    DeriveInput, Field, Fields, Ident, LitInt,
+};
+
+use crate::errors::MacroError;
+
+/// Expected layout given as `size = N, align = M`, both optional
+#[derive(Default)]
+pub(crate) struct LayoutArgs {
+    size: Option<LitInt>,
+    align: Option<LitInt>,
+}
+
+impl LayoutArgs {
+    pub(crate) fn parse(&mut self, meta: &ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("size") {
+            self.size = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("align") {
+            self.align = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("expected `size = N` or `align = N`"));
+        }
+        Ok(())
+    }
+}
+
+/// `FIELD_OFFSETS` table and compile time assertions of `name` layout
+///
+/// Assertions are `const` items, so a changed layout fails the build
+pub(crate) fn layout_items(
+    name: &Ident,
+    fields: &Punctuated<Field, Comma>,
+    args: &LayoutArgs,
+) -> TokenStream {
+    let offsets = fields.iter().filter_map(|field| {
+        let field_name = field.ident.as_ref()?;
+        let field_str = field_name.to_string();
+        Some(quote! { (#field_str, ::core::mem::offset_of!(#name, #field_name)) })
+    });
+
+    let size_assert = args.size.as_ref().map(|size| {
+        let msg = format!("Size of {} differs from declared {}", name, size);
+        quote! {
+            const _: () = assert!(::core::mem::size_of::<#name>() == #size, #msg);
+        }
+    });
+
+    let align_assert = args.align.as_ref().map(|align| {
+        let msg = format!("Alignment of {} differs from declared {}", name, align);
+        quote! {
+            const _: () = assert!(::core::mem::align_of::<#name>() == #align, #msg);
+        }
+    });
+
+    quote! {
+        impl #name {
+            /// `(field, offset)` of every field in declaration order
+            pub const FIELD_OFFSETS: &'static [(&'static str, usize)] = &[#(#offsets),*];
+        }
+
+        #size_assert
+        #align_assert
+    }
+}
+
+#[inline]
+pub(crate) fn drv_layout_inner(
+    input: &DeriveInput,
+    args: &LayoutArgs,
+) -> Result<TokenStream, MacroError> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields_named) => &fields_named.named,
+            _ => return Err(MacroError::UnnamedField(data.fields.span())),
+        },
+        _ => {
+            return Err(MacroError::IncorrectEntityType(
+                "Only structs are supported".to_string(),
+                input.span(),
+            ))
+        }
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(MacroError::IncorrectMacroUsage(
+            "Generic structs use `pod_wrapper` layout arguments".to_string(),
+            input.generics.span(),
+        ));
+    }
+
+    let layout = layout_items(&input.ident, fields, args);
+
+    Ok(quote! {
+        #input
+
+        #layout
+    })
+}
@@ -0,0 +1,156 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, Data, DeriveInput, Expr, Fields, Ident, Type};
+
+use crate::errors::MacroError;
+
+/// Fields with this prefix are padding, zeroed and hidden
+const PADDING_PREFIX: &str = "padding";
+
+/// Tag expression from `#[drv_log(tag = ...)]`
+fn parse_tag(input: &DeriveInput) -> Result<Expr, MacroError> {
+    let attr = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("drv_log"))
+        .ok_or_else(|| {
+            MacroError::IncorrectMacroUsage(
+                "expected `#[drv_log(tag = log_type::...)]`".to_string(),
+                input.ident.span(),
+            )
+        })?;
+
+    let mut tag = None;
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("tag") {
+            tag = Some(meta.value()?.parse::<Expr>()?);
+            Ok(())
+        } else {
+            Err(meta.error("expected `tag = ...`"))
+        }
+    })
+    .map_err(|err| MacroError::IncorrectMacroUsage(err.to_string(), err.span()))?;
+
+    tag.ok_or_else(|| {
+        MacroError::IncorrectMacroUsage("missing `tag = ...`".to_string(), attr.span())
+    })
+}
+
+/// Named fields after the leading `tag: u8`
+pub(crate) struct TaggedFields<'a> {
+    pub(crate) values: Vec<(&'a Ident, &'a Type)>,
+    pub(crate) padding: Vec<&'a Ident>,
+}
+
+/// Splits fields of a tagged struct into values and padding
+///
+/// Struct must be non-generic with named fields, the first one `tag`
+pub(crate) fn tagged_fields(input: &DeriveInput) -> Result<TaggedFields<'_>, MacroError> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields_named) => &fields_named.named,
+            _ => return Err(MacroError::UnnamedField(data.fields.span())),
+        },
+        _ => {
+            return Err(MacroError::IncorrectEntityType(
+                "Only structs are supported".to_string(),
+                input.span(),
+            ))
+        }
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(MacroError::UsedGeneric(
+            "parameter".to_string(),
+            input.generics.span(),
+        ));
+    }
+
+    match fields.first() {
+        Some(field) if field.ident.as_ref().is_some_and(|ident| ident == "tag") => {}
+        _ => {
+            return Err(MacroError::IncorrectInputType(
+                "First field must be `tag: u8`".to_string(),
+                fields.span(),
+            ))
+        }
+    }
+
+    let (padding, values): (Vec<_>, Vec<_>) = fields
+        .iter()
+        .skip(1)
+        .filter_map(|field| field.ident.as_ref().map(|ident| (ident, &field.ty)))
+        .partition(|(ident, _)| ident.to_string().starts_with(PADDING_PREFIX));
+
+    Ok(TaggedFields {
+        values,
+        padding: padding.into_iter().map(|(ident, _)| ident).collect(),
+    })
+}
+
+/// Binds `log_type` tag to the report
+///
+/// Generates `TAG`, constructor filling the tag and zeroing padding, checked
+/// `from_bytes` and `Display` without padding fields
+pub(crate) fn drv_log_inner(input: &DeriveInput) -> Result<TokenStream, MacroError> {
+    let name = &input.ident;
+    let TaggedFields { values, padding } = tagged_fields(input)?;
+    let tag = parse_tag(input)?;
+
+    let value_names = values.iter().map(|(ident, _)| ident).collect::<Vec<_>>();
+    let value_types = values.iter().map(|(_, ty)| ty);
+
+    let format = format!(
+        "{} {{{{ tag: {{}}{} }}}}",
+        name,
+        value_names
+            .iter()
+            .map(|ident| format!(", {}: {{:?}}", ident))
+            .collect::<String>()
+    );
+
+    Ok(quote! {
+        impl #name {
+            /// `log_type` tag of the report
+            pub const TAG: u8 = #tag;
+
+            /// Report with `TAG` and zeroed padding
+            #[allow(clippy::too_many_arguments)]
+            pub const fn new(#(#value_names: #value_types),*) -> Self {
+                Self {
+                    tag: Self::TAG,
+                    #(#value_names,)*
+                    #(#padding: 0,)*
+                }
+            }
+
+            /// Reads the report from its exact bytes
+            ///
+            /// ### Errors
+            /// - `InvalidDataLength` - data length differs from the report size
+            /// - `InvalidClientDataFormat` - tag differs from `TAG`
+            pub fn from_bytes(data: &[u8]) -> Result<Self, crate::errors::ModelsError> {
+                let size = ::core::mem::size_of::<Self>();
+                if data.len() != size {
+                    return Err(crate::errors::ModelsError::InvalidDataLength {
+                        expected: size,
+                        actual: data.len(),
+                    });
+                }
+
+                let report: Self = ::bytemuck::pod_read_unaligned(data);
+                if report.tag != Self::TAG {
+                    return Err(crate::errors::ModelsError::InvalidClientDataFormat);
+                }
+
+                Ok(report)
+            }
+        }
+
+        impl ::core::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                write!(f, #format, self.tag #(, self.#value_names)*)
+            }
+        }
+    })
+}
@@ -1,12 +1,20 @@
 use drv_account::drv_account_inner;
+use drv_instruction::drv_instruction_inner;
+use drv_layout::{drv_layout_inner, LayoutArgs};
+use drv_log::drv_log_inner;
 use new_type::new_type_inner;
-use syn::{parse_macro_input, Attribute, DeriveInput, ItemFn};
+use syn::{
+    parse_macro_input, punctuated::Punctuated, Attribute, DeriveInput, Ident, ItemFn, Token,
+};
 
 use proc_macro::TokenStream;
 
 use cu_stats::cu_stats_inner;
 mod cu_stats;
 mod drv_account;
+mod drv_instruction;
+mod drv_layout;
+mod drv_log;
 mod errors;
 mod new_type;
 
@@ -38,8 +46,17 @@ mod new_type;
 ///
 /// In case of existence of generic affecting memory layout for relatively
 /// simple types helpfull compile time error is generated.
+///
+/// ## Layout:
+/// `FIELD_OFFSETS` table is generated for both structures. Optional
+/// `#[pod_wrapper(size = N, align = M)]` arguments assert the inner structure
+/// layout at compile time.
+
+pub fn pod_wrapper(attrs: TokenStream, input: TokenStream) -> TokenStream {
+    let mut layout_args = LayoutArgs::default();
+    let parser = syn::meta::parser(|meta| layout_args.parse(&meta));
+    parse_macro_input!(attrs with parser);
 
-pub fn pod_wrapper(_attrs: TokenStream, input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let attrs: Vec<&Attribute> = input
         .attrs
@@ -47,12 +64,37 @@ pub fn pod_wrapper(_attrs: TokenStream, input: TokenStream) -> TokenStream {
         .filter(|attr| !attr.path().is_ident("pod_wrapper"))
         .collect();
 
-    let result = drv_account_inner(&input, attrs)
+    let result = drv_account_inner(&input, attrs, &layout_args)
         .unwrap_or_else(|err| err.to_syn_error().to_compile_error().into());
 
     proc_macro::TokenStream::from(result)
 }
 
+#[proc_macro_attribute]
+/// ## Layout macro
+/// `pod_wrapper` layout checks for non-generic structs.
+///
+/// ```ignore
+/// #[drv_layout(size = 24, align = 4)]
+/// #[repr(C)]
+/// struct Header { ... }
+/// ```
+///
+/// Generates `FIELD_OFFSETS` table and `const` assertions of given size and
+/// alignment, so accidental layout changes fail the build.
+pub fn drv_layout(attrs: TokenStream, input: TokenStream) -> TokenStream {
+    let mut layout_args = LayoutArgs::default();
+    let parser = syn::meta::parser(|meta| layout_args.parse(&meta));
+    parse_macro_input!(attrs with parser);
+
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let result = drv_layout_inner(&input, &layout_args)
+        .unwrap_or_else(|err| err.to_syn_error().to_compile_error());
+
+    TokenStream::from(result)
+}
+
 #[proc_macro_attribute]
 /// ## New type macro
 /// Macro is used to clean up the code of new types creation.
@@ -62,7 +104,26 @@ pub fn pod_wrapper(_attrs: TokenStream, input: TokenStream) -> TokenStream {
 /// attribute and find trait which can be used in new type patern.
 ///
 /// Etc. PartialEq, PartialOrd
-pub fn new_type(_attrs: TokenStream, input: TokenStream) -> TokenStream {
+///
+/// `value()` borrows the inner value, so any inner type is supported.
+///
+/// ## Forwarding:
+/// Additional traits of the inner type are forwarded on request
+///
+/// ```ignore
+/// #[drv_macros::new_type(Display, FromStr, Add, Sub, Hash, From, Into)]
+/// pub struct OrderId(pub i64);
+/// ```
+///
+/// 1. `Display`, `FromStr`, `Hash` - delegate to the inner value
+/// 2. `Add`, `Sub` - operators plus `checked_add`/`checked_sub`
+/// 3. `Serialize`, `Deserialize` - transparent, emitted unconditionally, callers
+///    with optional serde gate the whole attribute with `cfg_attr`
+/// 4. `From`, `Into` - conversions from and into the inner type
+pub fn new_type(attrs: TokenStream, input: TokenStream) -> TokenStream {
+    let forwards = parse_macro_input!(attrs with Punctuated::<Ident, Token![,]>::parse_terminated);
+    let forwards = forwards.into_iter().collect::<Vec<_>>();
+
     let input = parse_macro_input!(input as DeriveInput);
     let attrs: Vec<&Attribute> = input
         .attrs
@@ -70,20 +131,94 @@ pub fn new_type(_attrs: TokenStream, input: TokenStream) -> TokenStream {
         .filter(|attr| !attr.path().is_ident("new_type"))
         .collect();
 
-    let result = new_type_inner(&input, attrs)
+    let result = new_type_inner(&input, attrs, &forwards)
         .unwrap_or_else(|err| err.to_syn_error().to_compile_error().into());
 
     TokenStream::from(result)
 }
 
+#[proc_macro_derive(DrvLog, attributes(drv_log))]
+/// ## Log report macro
+/// Binds a `log_type` tag to a log report struct
+///
+/// ```ignore
+/// #[derive(DrvLog)]
+/// #[drv_log(tag = log_type::SPOT_FILL_ORDER)]
+/// pub struct SpotFillOrderReport { pub tag: u8, ... }
+/// ```
+///
+/// ## Requirements:
+/// 1. First field must be `tag: u8`
+/// 2. Padding fields are integers named `padding*`
+/// 3. Used within `drv-models`, errors are `crate::errors::ModelsError`
+///
+/// ## Macro output:
+/// 1. `TAG` constant
+/// 2. `new` constructor filling `tag` and zeroing padding
+/// 3. `from_bytes` checking data length and tag
+/// 4. `Display` skipping padding fields
+pub fn drv_log(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let result = drv_log_inner(&input).unwrap_or_else(|err| err.to_syn_error().to_compile_error());
+
+    TokenStream::from(result)
+}
+
+#[proc_macro_derive(DrvInstructionData, attributes(drv_instruction))]
+/// ## Instruction data macro
+/// Links an instruction payload to its `DrvInstruction` marker
+///
+/// ```ignore
+/// #[derive(DrvInstructionData)]
+/// #[drv_instruction(NewSpotOrderInstruction)]
+/// pub struct NewSpotOrderData { pub tag: u8, ... }
+/// ```
+///
+/// ## Requirements:
+/// 1. First field must be `tag: u8`, asserted at compile time
+/// 2. Padding fields are integers named `padding*`
+/// 3. Used within `drv-models`, errors are `crate::errors::ModelsError`
+///
+/// ## Macro output:
+/// 1. `TAG` constant equal to `INSTRUCTION_NUMBER`
+/// 2. `new` constructor filling `tag` and zeroing padding
+/// 3. `parse` checking data length and tag
+pub fn drv_instruction_data(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let result =
+        drv_instruction_inner(&input).unwrap_or_else(|err| err.to_syn_error().to_compile_error());
+
+    TokenStream::from(result)
+}
+
 #[proc_macro_attribute]
 /// ## Cu stats macro
-/// Macro is used for instructions spedning CU calculations
+/// Macro is used for instructions spending CU calculations
+///
+/// Function body is wrapped into a scope measured on every exit path. Scope
+/// is labelled by the function name or by an optional string literal:
+///
+/// ```ignore
+/// #[cu_stats("new_perp_order::match")]
+/// fn match_orders() -> ProgramResult { ... }
+/// ```
+///
+/// Each scope logs `cu:>label` followed by remaining compute units on entry,
+/// and remaining compute units followed by `cu:<label` on exit. Nested
+/// scopes form a tree parsed by `drv_models::cu_stats::CuTree`. Requires
+/// `solana-msg` dependency, instrumentation is compiled only for the `solana`
+/// target, so host builds need `cfg(target_os, values("solana"))` check-cfg
+/// like any program crate.
 pub fn cu_stats(
-    _attrs: proc_macro::TokenStream,
+    attrs: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
     let input_fn = parse_macro_input!(input as ItemFn);
 
-    proc_macro::TokenStream::from(cu_stats_inner(input_fn))
+    let result = cu_stats_inner(attrs.into(), input_fn)
+        .unwrap_or_else(|err| err.to_syn_error().to_compile_error());
+
+    proc_macro::TokenStream::from(result)
 }
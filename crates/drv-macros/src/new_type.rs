@@ -1,12 +1,12 @@
 use lazy_static::lazy_static;
 use std::collections::HashMap;
-use traits_implementations::get_implementation;
+use traits_implementations::{get_forwarding, get_implementation};
 
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{spanned::Spanned, Attribute, Data, DeriveInput, Fields, Ident, Type};
 
-use crate::errors::MacroError::{self, IncorrectInputType};
+use crate::errors::MacroError::{self, IncorrectInputType, IncorrectMacroUsage};
 
 mod traits_implementations {
     use super::*;
@@ -45,6 +45,114 @@ mod traits_implementations {
         }
     }
 
+    fn impl_display(name: &Ident, _: &Type) -> TokenStream {
+        quote! {
+            impl std::fmt::Display for #name {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    std::fmt::Display::fmt(&self.0, f)
+                }
+            }
+        }
+    }
+
+    fn impl_from_str(name: &Ident, field_type: &Type) -> TokenStream {
+        quote! {
+            impl std::str::FromStr for #name {
+                type Err = <#field_type as std::str::FromStr>::Err;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    s.parse().map(#name)
+                }
+            }
+        }
+    }
+
+    fn impl_add(name: &Ident, _: &Type) -> TokenStream {
+        quote! {
+            impl std::ops::Add for #name {
+                type Output = Self;
+
+                fn add(self, other: Self) -> Self {
+                    #name(self.0 + other.0)
+                }
+            }
+
+            impl #name {
+                pub fn checked_add(self, other: Self) -> Option<Self> {
+                    self.0.checked_add(other.0).map(#name)
+                }
+            }
+        }
+    }
+
+    fn impl_sub(name: &Ident, _: &Type) -> TokenStream {
+        quote! {
+            impl std::ops::Sub for #name {
+                type Output = Self;
+
+                fn sub(self, other: Self) -> Self {
+                    #name(self.0 - other.0)
+                }
+            }
+
+            impl #name {
+                pub fn checked_sub(self, other: Self) -> Option<Self> {
+                    self.0.checked_sub(other.0).map(#name)
+                }
+            }
+        }
+    }
+
+    fn impl_hash(name: &Ident, _: &Type) -> TokenStream {
+        quote! {
+            impl std::hash::Hash for #name {
+                fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                    std::hash::Hash::hash(&self.0, state)
+                }
+            }
+        }
+    }
+
+    fn impl_serialize(name: &Ident, _: &Type) -> TokenStream {
+        quote! {
+            impl ::serde::Serialize for #name {
+                fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    ::serde::Serialize::serialize(&self.0, serializer)
+                }
+            }
+        }
+    }
+
+    fn impl_deserialize(name: &Ident, field_type: &Type) -> TokenStream {
+        quote! {
+            impl<'de> ::serde::Deserialize<'de> for #name {
+                fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    <#field_type as ::serde::Deserialize<'de>>::deserialize(deserializer).map(#name)
+                }
+            }
+        }
+    }
+
+    fn impl_from(name: &Ident, field_type: &Type) -> TokenStream {
+        quote! {
+            impl From<#field_type> for #name {
+                fn from(value: #field_type) -> Self {
+                    #name(value)
+                }
+            }
+        }
+    }
+
+    fn impl_into(name: &Ident, field_type: &Type) -> TokenStream {
+        quote! {
+            impl From<#name> for #field_type {
+                fn from(value: #name) -> Self {
+                    value.0
+                }
+            }
+        }
+    }
+
     fn create_impl_map() -> HashMap<&'static str, ImplGenerator> {
         let mut map = HashMap::new();
 
@@ -57,8 +165,44 @@ mod traits_implementations {
         map
     }
 
+    fn create_forward_map() -> HashMap<&'static str, ImplGenerator> {
+        let mut map = HashMap::new();
+
+        let val: fn(&Ident, &Type) -> TokenStream = impl_display;
+        map.insert("Display", val);
+
+        let val: fn(&Ident, &Type) -> TokenStream = impl_from_str;
+        map.insert("FromStr", val);
+
+        let val: fn(&Ident, &Type) -> TokenStream = impl_add;
+        map.insert("Add", val);
+
+        let val: fn(&Ident, &Type) -> TokenStream = impl_sub;
+        map.insert("Sub", val);
+
+        let val: fn(&Ident, &Type) -> TokenStream = impl_hash;
+        map.insert("Hash", val);
+
+        let val: fn(&Ident, &Type) -> TokenStream = impl_serialize;
+        map.insert("Serialize", val);
+
+        let val: fn(&Ident, &Type) -> TokenStream = impl_deserialize;
+        map.insert("Deserialize", val);
+
+        let val: fn(&Ident, &Type) -> TokenStream = impl_from;
+        map.insert("From", val);
+
+        let val: fn(&Ident, &Type) -> TokenStream = impl_into;
+        map.insert("Into", val);
+
+        map
+    }
+
     lazy_static! {
+        /// Implementations added for traits listed in `derive`
         pub static ref TRAIT_IMPLS: HashMap<&'static str, ImplGenerator> = create_impl_map();
+        /// Implementations requested by `new_type` arguments
+        pub static ref FORWARD_IMPLS: HashMap<&'static str, ImplGenerator> = create_forward_map();
     }
 
     pub(crate) fn get_implementation(
@@ -70,6 +214,16 @@ mod traits_implementations {
             .get(trait_name)
             .map(|generator| generator(name, field_type))
     }
+
+    pub(crate) fn get_forwarding(
+        trait_name: &str,
+        name: &Ident,
+        field_type: &Type,
+    ) -> Option<TokenStream> {
+        FORWARD_IMPLS
+            .get(trait_name)
+            .map(|generator| generator(name, field_type))
+    }
 }
 
 /// As syn does not have standart parser for **derive**, custom "parser" is
@@ -101,6 +255,7 @@ fn get_derive_traits(attr: &Attribute) -> Vec<String> {
 pub(crate) fn new_type_inner(
     input: &DeriveInput,
     _: Vec<&Attribute>,
+    forwards: &[Ident],
 ) -> Result<TokenStream, MacroError> {
     let name = &input.ident;
 
@@ -152,6 +307,18 @@ pub(crate) fn new_type_inner(
         })
         .unwrap_or(vec![quote! {}]);
 
+    let forwarded_impls = forwards
+        .iter()
+        .map(|trait_name| {
+            get_forwarding(&trait_name.to_string(), name, field_type).ok_or_else(|| {
+                IncorrectMacroUsage(
+                    format!("Trait {} can not be forwarded", trait_name),
+                    trait_name.span(),
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
     Ok(quote! {
         #input
 
@@ -159,13 +326,17 @@ pub(crate) fn new_type_inner(
             #addititional_impls
         )*
 
+        #(
+            #forwarded_impls
+        )*
+
         impl #name {
             pub fn new(value: #field_type) -> Self {
                 #name(value)
             }
 
-            pub fn value(&self) -> u32 {
-                self.0
+            pub const fn value(&self) -> &#field_type {
+                &self.0
             }
         }
 
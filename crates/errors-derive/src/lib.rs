@@ -1,195 +1,577 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, Data, DeriveInput, Fields, Lit};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Fields, Ident, LitBool,
+    LitInt, LitStr, Path, Variant,
+};
 
-#[proc_macro_derive(DrvError, attributes(error))]
-pub fn solana_error_derive(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
+/// `category` attribute values with `ErrorCategory` variants
+const CATEGORIES: [(&str, &str); 5] = [
+    ("validation", "Validation"),
+    ("state", "State"),
+    ("risk", "Risk"),
+    ("system", "System"),
+    ("permission", "Permission"),
+];
 
-    let name = &input.ident;
-    let variants = match &input.data {
-        Data::Enum(data_enum) => &data_enum.variants,
-        _ => panic!("DrvError can only be derived for enums"),
+/// JSON rendering of a variant field
+enum Render {
+    /// `serde_json::json!(field)`, parsed back with `serde_json::from_value`
+    Value,
+    /// `field.to_string()`, parsed back with `FromStr`
+    Display,
+    /// Omitted from JSON, parsed back as `Default::default()`
+    Skip,
+    /// `path::to_json(&field)`, parsed back with `path::from_json(&value)`
+    With(Path),
+}
+
+struct FieldMeta {
+    /// Field name, or `_N` for tuple fields
+    binding: Ident,
+    /// JSON key and message placeholder, field name or position
+    key: String,
+    render: Render,
+}
+
+struct VariantMeta<'a> {
+    ident: &'a Ident,
+    code: u32,
+    /// Message as declared, listed in `ERROR_TABLE`
+    template: String,
+    /// Message as format string capturing bound fields
+    msg: String,
+    /// Intentionally shares the code of an earlier variant
+    alias: bool,
+    /// Shares the code of an unrelated earlier variant, kept for deployed
+    /// programs until the code is renumbered
+    known_collision: bool,
+    category: Ident,
+    retryable: bool,
+    named: bool,
+    fields: Vec<FieldMeta>,
+}
+
+impl VariantMeta<'_> {
+    /// Pattern binding fields named in `used`, others are ignored
+    fn pattern(&self, name: &Ident, used: &BTreeSet<String>) -> TokenStream2 {
+        let ident = self.ident;
+
+        if self.fields.is_empty() {
+            return quote! { #name::#ident };
+        }
+
+        if self.named {
+            let bindings = self
+                .fields
+                .iter()
+                .filter(|field| used.contains(&field.binding.to_string()))
+                .map(|field| &field.binding);
+            quote! { #name::#ident { #(#bindings,)* .. } }
+        } else {
+            let bindings = self.fields.iter().map(|field| {
+                let binding = &field.binding;
+                if used.contains(&field.binding.to_string()) {
+                    quote! { #binding }
+                } else {
+                    quote! { _ }
+                }
+            });
+            quote! { #name::#ident(#(#bindings),*) }
+        }
+    }
+
+    fn wildcard(&self, name: &Ident) -> TokenStream2 {
+        self.pattern(name, &BTreeSet::new())
+    }
+}
+
+/// Names referenced by `{name}` or `{name:spec}` placeholders of a format string
+fn placeholders(msg: &str) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let mut chars = msg.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        if char != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            continue;
+        }
+
+        let name = chars
+            .by_ref()
+            .take_while(|char| *char != '}' && *char != ':')
+            .collect::<String>();
+        names.insert(name);
+    }
+
+    names
+}
+
+/// Rewrites positional `{N}` placeholders to `{_N}` captures of tuple fields
+fn positional_to_captures(msg: &str) -> String {
+    let mut result = String::with_capacity(msg.len());
+    let mut chars = msg.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        result.push(char);
+        if char != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            result.extend(chars.next());
+            continue;
+        }
+        if chars.peek().is_some_and(char::is_ascii_digit) {
+            result.push('_');
+        }
+    }
+
+    result
+}
+
+fn combine(errors: Vec<syn::Error>) -> syn::Result<()> {
+    match errors.into_iter().reduce(|mut error, next| {
+        error.combine(next);
+        error
+    }) {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+fn parse_field_render(attrs: &[Attribute]) -> syn::Result<Render> {
+    let mut render = Render::Value;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("error")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("display") {
+                render = Render::Display;
+            } else if meta.path.is_ident("skip") {
+                render = Render::Skip;
+            } else if meta.path.is_ident("with") {
+                render = Render::With(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("Expected `display`, `skip` or `with = path`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(render)
+}
+
+fn parse_variant(variant: &Variant) -> syn::Result<VariantMeta<'_>> {
+    let mut code = None;
+    let mut msg = None;
+    let mut alias = false;
+    let mut known_collision = false;
+    let mut category = None;
+    let mut retryable = false;
+
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("error"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("code") {
+                let lit: LitInt = meta.value()?.parse()?;
+                code = Some(lit.base10_parse::<u32>()?);
+            } else if meta.path.is_ident("msg") {
+                let lit: LitStr = meta.value()?.parse()?;
+                msg = Some(lit.value());
+            } else if meta.path.is_ident("alias") {
+                let lit: LitStr = meta.value()?.parse()?;
+                if lit.value().trim().is_empty() {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "`alias` needs the reason the code is shared",
+                    ));
+                }
+                alias = true;
+            } else if meta.path.is_ident("known_collision") {
+                known_collision = true;
+            } else if meta.path.is_ident("category") {
+                let lit: LitStr = meta.value()?.parse()?;
+                let ident = CATEGORIES
+                    .iter()
+                    .find(|(value, _)| lit.value() == *value)
+                    .map(|(_, ident)| Ident::new(ident, lit.span()))
+                    .ok_or_else(|| {
+                        syn::Error::new(
+                            lit.span(),
+                            format!(
+                                "Unknown category, expected one of {:?}",
+                                CATEGORIES.map(|(value, _)| value)
+                            ),
+                        )
+                    })?;
+                category = Some(ident);
+            } else if meta.path.is_ident("retryable") {
+                let lit: LitBool = meta.value()?.parse()?;
+                retryable = lit.value;
+            } else {
+                return Err(meta.error(
+                    "Expected `code`, `msg`, `alias`, `known_collision`, `category` or `retryable`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+
+    let missing = |attribute: &str| {
+        syn::Error::new_spanned(
+            &variant.ident,
+            format!(
+                "Missing '{}' attribute for variant {}",
+                attribute, variant.ident
+            ),
+        )
     };
+    let code = code.ok_or_else(|| missing("code"))?;
+    let msg = msg.ok_or_else(|| missing("msg"))?;
+    let category = category.ok_or_else(|| missing("category"))?;
 
-    // Collect error metadata (same as before)
-    let mut error_meta = Vec::new();
+    let named = matches!(variant.fields, Fields::Named(_));
+    let fields = variant
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let (binding, key) = match &field.ident {
+                Some(ident) => (ident.clone(), ident.to_string()),
+                None => (format_ident!("_{}", index), index.to_string()),
+            };
+
+            Ok(FieldMeta {
+                binding,
+                key,
+                render: parse_field_render(&field.attrs)?,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let template = msg;
+    let msg = if named {
+        template.clone()
+    } else {
+        positional_to_captures(&template)
+    };
+
+    let keys = fields
+        .iter()
+        .map(|field| field.binding.to_string())
+        .collect::<BTreeSet<_>>();
+    if let Some(unknown) = placeholders(&msg)
+        .into_iter()
+        .find(|name| !keys.contains(name))
+    {
+        return Err(syn::Error::new_spanned(
+            &variant.ident,
+            format!(
+                "Message of variant {} references unknown field `{}`",
+                variant.ident, unknown
+            ),
+        ));
+    }
+
+    Ok(VariantMeta {
+        ident: &variant.ident,
+        code,
+        template,
+        msg,
+        alias,
+        known_collision,
+        category,
+        retryable,
+        named,
+        fields,
+    })
+}
+
+/// Codes must be unique, variants sharing a code of an earlier variant are
+/// marked `alias` or `known_collision` explicitly
+fn check_codes(variants: &[VariantMeta<'_>]) -> syn::Result<()> {
+    let mut canonical = BTreeMap::new();
+    let mut errors = Vec::new();
 
     for variant in variants {
-        let variant_name = &variant.ident;
-        let mut code = None;
-        let mut msg = None;
-
-        for attr in &variant.attrs {
-            if attr.path().is_ident("error") {
-                attr.parse_nested_meta(|meta| {
-                    if meta.path.is_ident("code") {
-                        let value = meta.value()?;
-                        let lit: Lit = value.parse()?;
-                        if let Lit::Int(lit_int) = lit {
-                            code = Some(lit_int.base10_parse::<u32>().unwrap());
-                        }
-                    } else if meta.path.is_ident("msg") {
-                        let value = meta.value()?;
-                        let lit: Lit = value.parse()?;
-                        if let Lit::Str(lit_str) = lit {
-                            msg = Some(lit_str.value());
-                        }
-                    }
-                    Ok(())
-                })
-                .unwrap();
+        let ident = variant.ident;
+        let code = variant.code;
+
+        if variant.alias && variant.known_collision {
+            errors.push(syn::Error::new_spanned(
+                ident,
+                format!(
+                    "Variant {} can not be both `alias` and `known_collision`",
+                    ident
+                ),
+            ));
+            continue;
+        }
+
+        let shared = variant.alias || variant.known_collision;
+        match (canonical.get(&code), shared) {
+            (None, false) => {
+                canonical.insert(code, ident.to_string());
             }
+            (Some(first), false) => errors.push(syn::Error::new_spanned(
+                ident,
+                format!(
+                    "Code {} of variant {} is already used by {}, mark it `alias = \"reason\"` if sharing is intended",
+                    code, ident, first
+                ),
+            )),
+            (None, true) => errors.push(syn::Error::new_spanned(
+                ident,
+                format!(
+                    "Variant {} shares code {} but no earlier variant uses it",
+                    ident, code
+                ),
+            )),
+            (Some(_), true) => {}
         }
+    }
 
-        let code = code.expect(&format!(
-            "Missing 'code' attribute for variant {}",
-            variant_name
-        ));
-        let msg = msg.expect(&format!(
-            "Missing 'msg' attribute for variant {}",
-            variant_name
+    combine(errors)
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let Data::Enum(data_enum) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "DrvError can only be derived for enums",
         ));
+    };
 
-        error_meta.push((variant_name, code, msg, variant.fields.clone()));
+    let mut variants = Vec::new();
+    let mut errors = Vec::new();
+    for variant in &data_enum.variants {
+        match parse_variant(variant) {
+            Ok(meta) => variants.push(meta),
+            Err(error) => errors.push(error),
+        }
     }
+    combine(errors)?;
+    check_codes(&variants)?;
+
+    // Generate ERROR_TABLE, from_code() and name()
+    let table_entries = variants.iter().map(|variant| {
+        let code = variant.code;
+        let variant_str = variant.ident.to_string();
+        let template = &variant.template;
+        quote! { (#code, #variant_str, #template) }
+    });
+
+    let known_collisions = variants
+        .iter()
+        .filter(|variant| variant.known_collision)
+        .map(|variant| variant.code)
+        .collect::<BTreeSet<_>>();
+    let known_collision_codes = known_collisions.iter();
 
-    // Generate code() method
-    let code_arms = error_meta.iter().map(|(variant_name, code, _, fields)| {
-        let pattern = match fields {
-            Fields::Unit => quote! { #name::#variant_name },
-            Fields::Named(_) => quote! { #name::#variant_name { .. } },
-            Fields::Unnamed(_) => quote! { #name::#variant_name(..) },
-        };
+    let from_code_arms = variants
+        .iter()
+        .enumerate()
+        .filter(|(_, variant)| {
+            !variant.alias && !variant.known_collision && !known_collisions.contains(&variant.code)
+        })
+        .map(|(index, variant)| {
+            let code = variant.code;
+            quote! { #code => Some(&Self::ERROR_TABLE[#index]) }
+        });
+
+    let name_arms = variants.iter().map(|variant| {
+        let pattern = variant.wildcard(name);
+        let variant_str = variant.ident.to_string();
+        quote! { #pattern => #variant_str }
+    });
+
+    // Generate code(), category() and is_retryable() methods
+    let code_arms = variants.iter().map(|variant| {
+        let pattern = variant.wildcard(name);
+        let code = variant.code;
         quote! { #pattern => #code }
     });
 
-    // Generate to_json() method
-    let json_arms = error_meta
+    let category_arms = variants.iter().map(|variant| {
+        let pattern = variant.wildcard(name);
+        let category = &variant.category;
+        quote! { #pattern => ::drv_errors::errors::ErrorCategory::#category }
+    });
+
+    let retryable_patterns = variants
         .iter()
-        .map(|(variant_name, code, msg, fields)| match fields {
-            Fields::Unit => {
-                quote! {
-                    #name::#variant_name => {
-                        serde_json::json!({
-                            "code": #code,
-                            "msg": #msg,
-                        })
-                    }
-                }
+        .filter(|variant| variant.retryable)
+        .map(|variant| variant.wildcard(name))
+        .collect::<Vec<_>>();
+    let is_retryable = if retryable_patterns.is_empty() {
+        quote! { false }
+    } else {
+        quote! { matches!(self, #(#retryable_patterns)|*) }
+    };
+
+    // Generate to_json() method, fields are rendered as declared by their
+    // `#[error(...)]` attribute
+    let json_arms = variants.iter().map(|variant| {
+        let rendered = variant
+            .fields
+            .iter()
+            .filter(|field| !matches!(field.render, Render::Skip))
+            .collect::<Vec<_>>();
+        let used = rendered
+            .iter()
+            .map(|field| field.binding.to_string())
+            .collect::<BTreeSet<_>>();
+        let pattern = variant.pattern(name, &used);
+
+        let json_fields = rendered.iter().map(|field| {
+            let key = &field.key;
+            let binding = &field.binding;
+            match &field.render {
+                Render::Display => quote! { #key: #binding.to_string() },
+                Render::With(path) => quote! { #key: #path::to_json(#binding) },
+                Render::Value | Render::Skip => quote! { #key: #binding },
             }
-            Fields::Named(fields_named) => {
-                let field_names: Vec<_> = fields_named
-                    .named
-                    .iter()
-                    .map(|f| f.ident.as_ref().unwrap())
-                    .collect();
-
-                let mut msg_format_str = msg.clone();
-                let mut format_args = Vec::new();
-
-                for field_name in &field_names {
-                    let field_str = field_name.to_string();
-                    let placeholder = format!("{{{}}}", field_str);
-                    if msg_format_str.contains(&placeholder) {
-                        msg_format_str = msg_format_str.replace(&placeholder, "{}");
-                        format_args.push(field_name);
-                    }
-                }
+        });
 
-                let json_fields = field_names.iter().map(|field_name| {
-                    let field_str = field_name.to_string();
-                    let needs_to_string = field_str.contains("account")
-                        || field_str.contains("address")
-                        || field_str.contains("pubkey")
-                        || field_str.contains("key")
-                        || field_str.contains("owner")
-                        || field_str.contains("authority");
-
-                    if needs_to_string {
-                        quote! { #field_str: #field_name.to_string() }
-                    } else {
-                        quote! { #field_str: #field_name }
-                    }
-                });
-
-                let msg_generation = if format_args.is_empty() {
-                    quote! { #msg_format_str.to_string() }
-                } else {
-                    quote! { format!(#msg_format_str, #(#format_args),*) }
-                };
+        quote! {
+            #pattern => serde_json::json!({
+                "code": self.code(),
+                "msg": self.to_string(),
+                #(#json_fields,)*
+            })
+        }
+    });
 
-                quote! {
-                    #name::#variant_name { #(#field_names),* } => {
-                        serde_json::json!({
-                            "code": #code,
-                            "msg": #msg_generation,
-                            #(#json_fields),*
-                        })
-                    }
+    // Generate from_json() arms indexed as ERROR_TABLE
+    let from_json_arms = variants.iter().enumerate().map(|(index, variant)| {
+        let ident = variant.ident;
+        let parsed_fields = variant.fields.iter().map(|field| {
+            let key = &field.key;
+            match &field.render {
+                Render::Value => {
+                    quote! { serde_json::from_value(json.get(#key)?.clone()).ok()? }
                 }
-            }
-            Fields::Unnamed(_) => {
-                panic!("Unnamed fields are not supported for DrvError");
+                Render::Display => quote! { json.get(#key)?.as_str()?.parse().ok()? },
+                Render::Skip => quote! { Default::default() },
+                Render::With(path) => quote! { #path::from_json(json.get(#key)?)? },
             }
         });
 
-    // Generate Display implementation
-    let display_arms = error_meta
-        .iter()
-        .map(|(variant_name, _, msg, fields)| match fields {
-            Fields::Unit => {
-                quote! {
-                    #name::#variant_name => write!(f, #msg)
+        if variant.fields.is_empty() {
+            quote! { #index => Some(#name::#ident) }
+        } else if variant.named {
+            let bindings = variant.fields.iter().map(|field| &field.binding);
+            quote! { #index => Some(#name::#ident { #(#bindings: #parsed_fields),* }) }
+        } else {
+            quote! { #index => Some(#name::#ident(#(#parsed_fields),*)) }
+        }
+    });
+
+    // Generate Display implementation, placeholders capture bound fields
+    let display_arms = variants.iter().map(|variant| {
+        let pattern = variant.pattern(name, &placeholders(&variant.msg));
+        let msg = &variant.msg;
+        quote! { #pattern => write!(f, #msg) }
+    });
+
+    Ok(quote! {
+        impl #name {
+            /// `(code, variant, msg)` of every variant in declaration order
+            pub const ERROR_TABLE: &'static [(u32, &'static str, &'static str)] = &[
+                #(#table_entries),*
+            ];
+
+            pub fn code(&self) -> u32 {
+                match self {
+                    #(#code_arms),*
                 }
             }
-            Fields::Named(fields_named) => {
-                let field_names: Vec<_> = fields_named
-                    .named
-                    .iter()
-                    .map(|f| f.ident.as_ref().unwrap())
-                    .collect();
-
-                let mut msg_format_str = msg.clone();
-                let mut format_args = Vec::new();
-
-                for field_name in &field_names {
-                    let field_str = field_name.to_string();
-                    let placeholder = format!("{{{}}}", field_str);
-                    if msg_format_str.contains(&placeholder) {
-                        msg_format_str = msg_format_str.replace(&placeholder, "{}");
-                        format_args.push(field_name);
-                    }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    #(#name_arms),*
                 }
+            }
 
-                if format_args.is_empty() {
-                    quote! {
-                        #name::#variant_name { .. } => write!(f, #msg_format_str)
-                    }
-                } else {
-                    quote! {
-                        #name::#variant_name { #(#field_names),* } => {
-                            write!(f, #msg_format_str, #(#format_args),*)
-                        }
-                    }
+            /// Codes shared by unrelated variants, pending renumbering
+            pub const KNOWN_COLLISIONS: &'static [u32] = &[#(#known_collision_codes),*];
+
+            /// `ERROR_TABLE` entry of the variant owning `code`, aliases
+            /// resolve to the first variant declared with the code
+            ///
+            /// `None` for `KNOWN_COLLISIONS`, their variants are listed by
+            /// `entries_with_code`
+            pub fn from_code(code: u32) -> Option<&'static (u32, &'static str, &'static str)> {
+                match code {
+                    #(#from_code_arms,)*
+                    _ => None,
                 }
             }
-            Fields::Unnamed(_) => {
-                panic!("Unnamed fields are not supported for DrvError");
+
+            /// `ERROR_TABLE` entries of every variant declared with `code`
+            pub fn entries_with_code(
+                code: u32,
+            ) -> impl Iterator<Item = &'static (u32, &'static str, &'static str)> {
+                Self::ERROR_TABLE.iter().filter(move |entry| entry.0 == code)
             }
-        });
 
-    let expanded = quote! {
-        impl #name {
-            pub fn code(&self) -> u32 {
+            pub fn category(&self) -> ::drv_errors::errors::ErrorCategory {
                 match self {
-                    #(#code_arms),*
+                    #(#category_arms),*
                 }
             }
 
+            /// Whether resending the same instruction later may succeed
+            pub fn is_retryable(&self) -> bool {
+                #is_retryable
+            }
+
             pub fn to_json(&self) -> serde_json::Value {
-                match self {
+                let mut json = match self {
                     #(#json_arms),*
+                };
+                json["category"] = serde_json::json!(self.category());
+                json["retryable"] = serde_json::json!(self.is_retryable());
+                json
+            }
+
+            /// Parses `to_json` output back into the variant
+            ///
+            /// Variants sharing the code are told apart by fields and the
+            /// rendered message, the first parsed variant is returned if no
+            /// message matches
+            pub fn from_json(json: &serde_json::Value) -> Option<Self> {
+                let code = u32::try_from(json.get("code")?.as_u64()?).ok()?;
+                let msg = json.get("msg").and_then(|msg| msg.as_str());
+                let mut parsed = Self::ERROR_TABLE
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, entry)| entry.0 == code)
+                    .filter_map(|(index, _)| Self::variant_from_json(index, json))
+                    .collect::<Vec<_>>();
+
+                match parsed
+                    .iter()
+                    .position(|error| msg.is_some_and(|msg| error.to_string() == msg))
+                {
+                    Some(position) => Some(parsed.swap_remove(position)),
+                    None => parsed.into_iter().next(),
+                }
+            }
+
+            fn variant_from_json(index: usize, json: &serde_json::Value) -> Option<Self> {
+                match index {
+                    #(#from_json_arms,)*
+                    _ => None,
                 }
             }
         }
@@ -203,7 +585,29 @@ pub fn solana_error_derive(input: TokenStream) -> TokenStream {
         }
 
         impl std::error::Error for #name {}
-    };
+    })
+}
+
+/// Derives `code`, `name`, `category`, `is_retryable`, `to_json`, `from_json`,
+/// `from_code`, `ERROR_TABLE` and `Display`
+///
+/// Every variant takes `#[error(code = N, msg = "...", category = "...")]`
+/// with an optional `retryable = bool`, categories map to
+/// `drv_errors::errors::ErrorCategory` variants. A variant reusing the code
+/// of an earlier variant must add `alias = "reason"` when the meanings are
+/// related, or `known_collision` when they are not and the code awaits
+/// renumbering, otherwise compilation fails.
+///
+/// Messages reference named fields as `{name}` and tuple fields as `{0}`.
+/// Fields are rendered in JSON with their `Serialize` impl unless marked
+/// `#[error(display)]` (`to_string`/`FromStr`), `#[error(skip)]` (omitted,
+/// parsed back as `Default`) or `#[error(with = path)]`
+/// (`path::to_json(&T) -> Value`/`path::from_json(&Value) -> Option<T>`).
+#[proc_macro_derive(DrvError, attributes(error))]
+pub fn solana_error_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
 
-    TokenStream::from(expanded)
+    expand(&input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
 }
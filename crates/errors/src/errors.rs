@@ -3,6 +3,7 @@ use std::{error, path::Display};
 
 use drv_models::{
     constants::TradingSection,
+    errors::ModelsError,
     new_types::instrument::InstrId,
     state::types::{
         account_type::AccountType,
@@ -32,6 +33,94 @@ impl From<ProgramError> for DeriverseErrorKind {
     }
 }
 
+impl From<ModelsError> for DeriverseErrorKind {
+    fn from(e: ModelsError) -> Self {
+        match e {
+            ModelsError::InvalidDataLength { expected, actual } => {
+                Self::InvalidDataLength { expected, actual }
+            }
+            ModelsError::InvalidQuantity {
+                value,
+                min_value,
+                max_value,
+            } => Self::InvalidQuantity {
+                value,
+                min_value,
+                max_value,
+            },
+            ModelsError::InvalidPrice {
+                price,
+                min_price,
+                max_price,
+            } => Self::InvalidPrice {
+                price,
+                min_price,
+                max_price,
+            },
+            ModelsError::InvalidLeverage { value, max } => Self::InvalidLeverage { value, max },
+            ModelsError::InvalidAccountTag {
+                expected_account_type,
+                actual_tag,
+                address,
+            } => Self::InvalidAccountTag {
+                expected_account_type,
+                actual_tag,
+                address,
+            },
+            ModelsError::InvalidVersion {
+                address,
+                account_type,
+                expected,
+                actual,
+            } => Self::InvalidVersion {
+                address,
+                account_type,
+                expected,
+                actual,
+            },
+            ModelsError::InvalidDataAlignment { address } => Self::InvalidDataAlignment { address },
+            ModelsError::OffsetOutOfBounds {
+                address,
+                offset,
+                len,
+            } => Self::OffsetOutOfBounds {
+                address,
+                offset,
+                len,
+            },
+            ModelsError::InvalidAccountSize {
+                address,
+                account_type,
+                expected,
+                actual,
+            } => Self::InvalidAccountSize {
+                address,
+                account_type,
+                expected,
+                actual,
+            },
+            ModelsError::UnknownOrderType { order_type_raw } => {
+                Self::UnknownOrderType { order_type_raw }
+            }
+            ModelsError::InvalidClientDataFormat => Self::InvalidClientDataFormat,
+            ModelsError::OrderWasNotFound { link, side } => Self::OrderWasNotFound { link, side },
+            ModelsError::InvalidBidOrdersCount => Self::InvalidBidOrdersCount,
+            ModelsError::InvalidAskOrdersCount => Self::InvalidAskOrdersCount,
+            ModelsError::InvalidBidLinesCount => Self::InvalidBidLinesCount,
+            ModelsError::InvalidAskLinesCount => Self::InvalidAskLinesCount,
+            ModelsError::ArithmeticOverflow => Self::ArithmeticOverflow,
+            ModelsError::InvalidQuoteOrdersAmount {
+                mask_amount,
+                orders_amount,
+            } => Self::InvalidQuoteOrdersAmount {
+                mask_amount,
+                orders_amount,
+            },
+            ModelsError::DivisionByZero => Self::DivisionByZero,
+        }
+    }
+}
+
 impl From<DeriverseErrorKind> for ProgramError {
     fn from(e: DeriverseErrorKind) -> Self {
         msg!("{}", e.to_json().to_string());
@@ -52,13 +141,40 @@ impl From<DeriverseError> for u32 {
     }
 }
 
+/// Error category, lets clients handle families of errors alike
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorCategory {
+    /// Invalid instruction data or accounts
+    Validation,
+    /// Current program state does not allow the operation
+    State,
+    /// Rejected by trading or margin checks
+    Risk,
+    /// Internal program failure
+    System,
+    /// Signer lacks rights for the operation
+    Permission,
+}
+
+/// `(code, variant, msg)` of every `DeriverseErrorKind` variant, maps
+/// `ProgramError::Custom` codes back to names off-chain
+pub static ERROR_TABLE: &[(u32, &str, &str)] = DeriverseErrorKind::ERROR_TABLE;
+
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct ErrorLocation {
     pub file: &'static str,
     pub line: u32,
 }
 
-#[derive(Debug)]
+/// `ErrorLocation` read back from logs, owns the file name
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ParsedErrorLocation {
+    pub file: String,
+    pub line: u32,
+}
+
+#[derive(Debug, PartialEq)]
 pub struct DeriverseError {
     pub error: DeriverseErrorKind,
     pub location: ErrorLocation,
@@ -88,6 +204,33 @@ impl DeriverseError {
     }
 }
 
+/// `DeriverseError` read back from logs
+#[derive(Debug, PartialEq)]
+pub struct ParsedDeriverseError {
+    pub error: DeriverseErrorKind,
+    pub location: ParsedErrorLocation,
+}
+
+impl ParsedDeriverseError {
+    /// Parses `DeriverseError::to_json` output, `None` if it has no
+    /// `location`
+    pub fn from_json(json: &serde_json::Value) -> Option<Self> {
+        Some(Self {
+            error: DeriverseErrorKind::from_json(json)?,
+            location: serde_json::from_value(json.get("location")?.clone()).ok()?,
+        })
+    }
+
+    /// Parses an error logged by `From<DeriverseError> for ProgramError`,
+    /// with or without the `Program log: ` prefix
+    pub fn from_log_line(line: &str) -> Option<Self> {
+        let line = line.trim();
+        let json = line.strip_prefix("Program log: ").unwrap_or(line);
+
+        Self::from_json(&serde_json::from_str(json).ok()?)
+    }
+}
+
 #[macro_export]
 macro_rules! drv_err {
     ($error:expr) => {
@@ -130,69 +273,114 @@ fn some_test() {
 
 #[derive(Debug, DrvError, Serialize, Deserialize, PartialEq)]
 pub enum DeriverseErrorKind {
-    #[error(code = 100, msg = "System error {error}")]
+    #[error(code = 100, msg = "System error {error}", category = "system")]
     SystemError { error: ProgramError },
 
     #[error(
         code = 101,
-        msg = "Invalid provided accounts number: expected {expected}, got {actual}"
+        msg = "Invalid provided accounts number: expected {expected}, got {actual}",
+        category = "validation"
     )]
     InvalidAccountsNumber { expected: usize, actual: usize },
 
     #[error(
         code = 102,
-        msg = "Invalid instructions data length, expected {expected} < actual {actual}"
+        msg = "Invalid instructions data length, expected {expected} < actual {actual}",
+        category = "validation"
     )]
     InvalidDataLength { expected: usize, actual: usize },
 
-    #[error(code = 104, msg = "{address} must be signer")]
-    MustBeSigner { address: Pubkey },
+    #[error(code = 104, msg = "{address} must be signer", category = "permission")]
+    MustBeSigner {
+        #[error(display)]
+        address: Pubkey,
+    },
 
-    #[error(code = 246, msg = "{address} must be read only")]
-    MustBeReadOnly { address: Pubkey },
+    #[error(
+        code = 246,
+        msg = "{address} must be read only",
+        category = "validation"
+    )]
+    MustBeReadOnly {
+        #[error(display)]
+        address: Pubkey,
+    },
 
-    #[error(code = 246, msg = "{address} must be writable")]
-    MustBeWritable { address: Pubkey },
+    #[error(
+        code = 246,
+        alias = "access flag of the same account",
+        msg = "{address} must be writable",
+        category = "validation"
+    )]
+    MustBeWritable {
+        #[error(display)]
+        address: Pubkey,
+    },
     #[error(
         code = 122,
-        msg = "Invalid token program ID, expected {expected} != actual {actual}"
+        msg = "Invalid token program ID, expected {expected} != actual {actual}",
+        category = "validation"
     )]
     InvalidTokenProgramId {
         expected: TokenProgram,
         actual: TokenProgram,
     },
-    #[error(code = 123, msg = "Unsupported token program id")]
-    UnsupportedTokenProgramId { address: Pubkey },
+    #[error(
+        code = 123,
+        msg = "Unsupported token program id",
+        category = "validation"
+    )]
+    UnsupportedTokenProgramId {
+        #[error(display)]
+        address: Pubkey,
+    },
 
-    #[error(code = 124, msg = "Invalid mint address for token {token_id}")]
+    #[error(
+        code = 124,
+        msg = "Invalid mint address for token {token_id}",
+        category = "validation"
+    )]
     InvalidMintAccount {
         token_id: u32,
+        #[error(display)]
         expected_address: Pubkey,
+        #[error(display)]
         actual_address: Pubkey,
     },
     #[error(
         code = 126,
-        msg = "Invalid mint token program, expected {expected} != actual {actual}"
+        msg = "Invalid mint token program, expected {expected} != actual {actual}",
+        category = "validation"
     )]
     InvalidMintProgramId {
         expected: TokenProgram,
         actual: TokenProgram,
+        #[error(display)]
         mint_address: Pubkey,
     },
-    #[error(code = 127, msg = "Invalid LUT program ID")]
-    InvalidLutProgramId { actual_address: Pubkey },
+    #[error(code = 127, msg = "Invalid LUT program ID", category = "validation")]
+    InvalidLutProgramId {
+        #[error(display)]
+        actual_address: Pubkey,
+    },
 
-    #[error(code = 128, msg = "Invalid LUT account")]
+    #[error(code = 128, msg = "Invalid LUT account", category = "validation")]
     InvalidLutAccount {
+        #[error(display)]
         expected_address: Pubkey,
+        #[error(display)]
         actual_address: Pubkey,
     },
 
-    #[error(code = 129, msg = "Invalid System program ID")]
-    InvalidSystemProgramId { actual_address: Pubkey },
+    #[error(code = 129, msg = "Invalid System program ID", category = "validation")]
+    InvalidSystemProgramId {
+        #[error(display)]
+        actual_address: Pubkey,
+    },
     #[error(
         code = 130,
-        msg = "Invalid quantity value {value}, acceptable range: {min_value}..{max_value}"
+        msg = "Invalid quantity value {value}, acceptable range: {min_value}..{max_value}",
+        category = "validation"
     )]
     InvalidQuantity {
         value: i64,
@@ -201,266 +389,396 @@ pub enum DeriverseErrorKind {
     },
     #[error(
         code = 131,
-        msg = "Invalid price value {price}, acceptable range: {min_price}..{max_price}"
+        msg = "Invalid price value {price}, acceptable range: {min_price}..{max_price}",
+        category = "validation"
     )]
     InvalidPrice {
         price: i64,
         min_price: i64,
         max_price: i64,
     },
-    #[error(code = 132, msg = "Insufficient funds")]
+    #[error(code = 132, msg = "Insufficient funds", category = "risk")]
     InsufficientFunds,
 
     #[error(
         code = 136,
-        msg = "Too many lines on {side} side provided value is {amount}"
+        msg = "Too many lines on {side} side provided value is {amount}",
+        category = "validation"
     )]
     TooManyLines { side: OrderSide, amount: u32 },
-    #[error(code = 137, msg = "Allocator failed")]
+    #[error(code = 137, msg = "Allocator failed", category = "system")]
     AllocatorFailed,
 
-    #[error(code = 137, msg = "Order allocator failed, side: {side}")]
+    #[error(
+        code = 137,
+        alias = "allocator failure, side only narrows it",
+        msg = "Order allocator failed, side: {side}",
+        category = "system"
+    )]
     OrderAllocatorFailed { side: OrderSide },
     #[error(
         code = 138,
-        msg = "Attempted to trade with yourself, order id: {order_id}"
+        msg = "Attempted to trade with yourself, order id: {order_id}",
+        category = "risk"
     )]
     CrossOrder { order_id: i64, qty: i64, sum: i64 },
 
-    #[error(code = 139, msg = "Engine matching failed")]
+    #[error(code = 139, msg = "Engine matching failed", category = "system")]
     MatchingEngineFailed,
 
-    #[error(code = 141, msg = "Invalid PDA for {account_type}")]
+    #[error(
+        code = 141,
+        msg = "Invalid PDA for {account_type}",
+        category = "validation"
+    )]
     InvalidPDA {
+        #[error(display)]
         expected_address: Pubkey,
+        #[error(display)]
         actual_address: Pubkey,
+        #[error(display)]
         account_type: AccountType,
     },
 
     #[error(
         code = 144,
-        msg = "Invalid new {account_type} account. Owner has to be SystemProgram"
+        msg = "Invalid new {account_type} account. Owner has to be SystemProgram",
+        category = "validation"
     )]
     InvalidNewAccount {
+        #[error(display)]
         address: Pubkey,
+        #[error(display)]
         owner: Pubkey,
+        #[error(display)]
         account_type: AccountType,
     },
     #[error(
         code = 143,
-        msg = "Invalid program {account_type} account. Owner have to be {program_id_address}"
+        msg = "Invalid program {account_type} account. Owner have to be {program_id_address}",
+        category = "validation"
     )]
     InvalidProgramAccount {
+        #[error(display)]
         address: Pubkey,
+        #[error(display)]
         owner: Pubkey,
+        #[error(display)]
         program_id_address: Pubkey,
+        #[error(display)]
         account_type: AccountType,
     },
-    #[error(code = 146, msg = "Invalid Holder Admin account")]
-    InvalidHolderAdminAccount { actual_address: Pubkey },
-    #[error(code = 147, msg = "Invalid Admin account")]
+    #[error(
+        code = 146,
+        msg = "Invalid Holder Admin account",
+        category = "permission"
+    )]
+    InvalidHolderAdminAccount {
+        #[error(display)]
+        actual_address: Pubkey,
+    },
+    #[error(code = 147, msg = "Invalid Admin account", category = "permission")]
     InvalidAdminAccount {
+        #[error(display)]
         expected_address: Pubkey,
+        #[error(display)]
         actual_address: Pubkey,
     },
     #[error(
         code = 148,
-        msg = "Invalid new Operator account, operator with this address already exist"
+        msg = "Invalid new Operator account, operator with this address already exist",
+        category = "state"
     )]
-    InvalidNewOperatorAccount { address: Pubkey },
+    InvalidNewOperatorAccount {
+        #[error(display)]
+        address: Pubkey,
+    },
     #[error(
         code = 150,
-        msg = "Invalid Operator account, operator with this address does not exist"
+        msg = "Invalid Operator account, operator with this address does not exist",
+        category = "permission"
     )]
-    InvalidOperatorAccount { address: Pubkey },
-    #[error(code = 151, msg = "Invalid token id {id}")]
+    InvalidOperatorAccount {
+        #[error(display)]
+        address: Pubkey,
+    },
+    #[error(code = 151, msg = "Invalid token id {id}", category = "validation")]
     InvalidTokenId { id: u32 },
     #[error(
         code = 151,
-        msg = "Unexpected token id, expected {expected} != actual {actual}"
+        alias = "token id check, fields add the mismatch",
+        msg = "Unexpected token id, expected {expected} != actual {actual}",
+        category = "validation"
     )]
     UnexpectedTokenId { expected: u32, actual: u32 },
-    #[error(code = 212, msg = "Token with id {id} was not found")]
+    #[error(
+        code = 212,
+        msg = "Token with id {id} was not found",
+        category = "state"
+    )]
     TokenWasNotFound {
         id: u32,
+        #[error(display)]
         community_state_address: Pubkey,
     },
     #[error(
         code = 155,
-        msg = "Invalid program's token account for {token_id} token id"
+        msg = "Invalid program's token account for {token_id} token id",
+        category = "validation"
     )]
     InvalidProgramsTokenAccount {
         token_id: u32,
+        #[error(display)]
         expected_address: Pubkey,
+        #[error(display)]
         actual_address: Pubkey,
     },
-    #[error(code = 166, msg = "Invalid instrument id {id}")]
+    #[error(
+        code = 166,
+        msg = "Invalid instrument id {id}",
+        category = "validation"
+    )]
     InvalidInstrId { id: u32 },
 
     #[error(
         code = 166,
-        msg = "Invalid instrument id in {account_type:?} account, expected {expected} != actual {actual}"
+        alias = "instrument id check, fields add the account",
+        msg = "Invalid instrument id in {account_type:?} account, expected {expected} != actual {actual}",
+        category = "validation"
     )]
     UnexpectedInstrumentId {
         expected: u32,
         actual: u32,
+        #[error(display)]
         address: Pubkey,
+        #[error(display)]
         account_type: AccountType,
     },
     #[error(
         code = 175,
-        msg = "Order node on the side {side} with id {id} was not found"
+        msg = "Order node on the side {side} with id {id} was not found",
+        category = "state"
     )]
     NodeWasNotFound { id: i64, side: OrderSide },
 
     #[error(
         code = 175,
-        msg = "Order on the side {side} with link {link} was not found"
+        alias = "order tree lookup, fields add the order",
+        msg = "Order on the side {side} with link {link} was not found",
+        category = "state"
     )]
     OrderWasNotFound { link: u32, side: OrderSide },
 
-    #[error(code = 183, msg = "Client data destruction")]
+    #[error(code = 183, msg = "Client data destruction", category = "system")]
     ClientDataDestruction,
-    #[error(code = 185, msg = "Token with id {id} must be base currency")]
+    #[error(
+        code = 185,
+        msg = "Token with id {id} must be base currency",
+        category = "validation"
+    )]
     TokenMustBeBaseCrncy { id: u32, mask: u32 },
 
-    #[error(code = 189, msg = "Impossible to upgrade instrument with id {id}")]
+    #[error(
+        code = 189,
+        msg = "Impossible to upgrade instrument with id {id}",
+        category = "state"
+    )]
     ImpossibleToUpgrade { id: u32, mask: u32 },
 
-    #[error(code = 190, msg = "Invalid Bid Orders count")]
+    #[error(code = 190, msg = "Invalid Bid Orders count", category = "system")]
     InvalidBidOrdersCount,
-    #[error(code = 191, msg = "Invalid Ask Orders count")]
+    #[error(code = 191, msg = "Invalid Ask Orders count", category = "system")]
     InvalidAskOrdersCount,
 
-    #[error(code = 192, msg = "Invalid Bid Lines count")]
+    #[error(code = 192, msg = "Invalid Bid Lines count", category = "system")]
     InvalidBidLinesCount,
 
-    #[error(code = 193, msg = "Invalid Ask Lines count")]
+    #[error(code = 193, msg = "Invalid Ask Lines count", category = "system")]
     InvalidAskLinesCount,
 
     #[error(
         code = 196,
-        msg = "Invalid associated token address for token {token_id}"
+        msg = "Invalid associated token address for token {token_id}",
+        category = "validation"
     )]
     InvalidAssociatedTokenAddress {
         token_id: u32,
+        #[error(display)]
         expected_address: Pubkey,
+        #[error(display)]
         actual_address: Pubkey,
     },
     #[error(
         code = 201,
-        msg = "Too early to distribute funds, current time is {current_time} < allowed time {limit_time}"
+        msg = "Too early to distribute funds, current time is {current_time} < allowed time {limit_time}",
+        category = "state",
+        retryable = true
     )]
     TooEarlyToDistribFunds { limit_time: u32, current_time: u32 },
 
     #[error(
         code = 203,
-        msg = "Insufficient Deriverse tokens supply, amount {amount} < min_amount {min_amount}"
+        msg = "Insufficient Deriverse tokens supply, amount {amount} < min_amount {min_amount}",
+        category = "risk"
     )]
     InsufficientDeriverseTokensSupply { amount: i64, min_amount: i64 },
 
-    #[error(code = 204, msg = "Invalid client Id in client community account")]
+    #[error(
+        code = 204,
+        msg = "Invalid client Id in client community account",
+        category = "validation"
+    )]
     InvalidClientId {
+        #[error(display)]
         address: Pubkey,
         expected: u32,
         actual: u32,
     },
     #[error(
         code = 205,
-        msg = "Invalid voting counter, expected {expected} != actual {actual}"
+        msg = "Invalid voting counter, expected {expected} != actual {actual}",
+        category = "state",
+        retryable = true
     )]
     InvalidVotingCounter { actual: u32, expected: u32 },
 
-    #[error(code = 206, msg = "Already voted")]
+    #[error(code = 206, msg = "Already voted", category = "state")]
     AlreadyVoted,
 
-    #[error(code = 210, msg = "No Trade (IOC)")]
+    #[error(
+        code = 210,
+        msg = "No Trade (IOC)",
+        category = "risk",
+        retryable = true
+    )]
     NoTradeIOC,
 
-    #[error(code = 212, msg = "Asset {asset_type} with id {id} was not found")]
+    #[error(
+        code = 212,
+        alias = "same lookup failure for assets",
+        msg = "Asset {asset_type} with id {id} was not found",
+        category = "state"
+    )]
     AssetNotFound { asset_type: AssetType, id: u32 },
 
-    #[error(code = 214, msg = "Null Pointer")]
+    #[error(code = 214, msg = "Null Pointer", category = "system")]
     NullPointer,
 
-    #[error(code = 217, msg = "Community account has to be read only")]
-    CommunityAccountHasToBeReadOnly { address: Pubkey },
+    #[error(
+        code = 217,
+        msg = "Community account has to be read only",
+        category = "validation"
+    )]
+    CommunityAccountHasToBeReadOnly {
+        #[error(display)]
+        address: Pubkey,
+    },
 
-    #[error(code = 218, msg = "Invalid token type")]
+    #[error(code = 218, msg = "Invalid token type", category = "validation")]
     InvalidTokenType,
 
-    #[error(code = 219, msg = "Null Index")]
+    #[error(code = 219, msg = "Null Index", category = "system")]
     NullIndex,
-    #[error(code = 223, msg = "Arithmetic overflow")]
+    #[error(code = 223, msg = "Arithmetic overflow", category = "system")]
     ArithmeticOverflow,
 
-    #[error(code = 224, msg = "Invalid data format")]
+    #[error(code = 224, msg = "Invalid data format", category = "validation")]
     InvalidClientDataFormat,
 
     #[error(
         code = 225,
-        msg = "Invalid order id {value}, acceptable range: {min_value}.."
+        msg = "Invalid order id {value}, acceptable range: {min_value}..",
+        category = "validation"
     )]
     InvalidOrderId {
         value: i64,
         min_value: i64,
         max_value: i64,
     },
-    #[error(code = 226, msg = "Perp on instrument id {id} is not available")]
+    #[error(
+        code = 226,
+        msg = "Perp on instrument id {id} is not available",
+        category = "state"
+    )]
     PerpIsNotAvailable { id: u32, mask: u32 },
 
-    #[error(code = 229, msg = "Impossible to withdraw funds during margin call")]
+    #[error(
+        code = 229,
+        msg = "Impossible to withdraw funds during margin call",
+        category = "risk"
+    )]
     ImpossibleToWithdrawFundsDuringMarginCall,
 
-    #[error(code = 230, msg = "Perp client count can not be zero")]
+    #[error(
+        code = 230,
+        msg = "Perp client count can not be zero",
+        category = "state"
+    )]
     PerpClientCountCanNotBeZero,
 
-    #[error(code = 231, msg = "Max perp clients count reached")]
+    #[error(code = 231, msg = "Max perp clients count reached", category = "state")]
     MaxPerpClientsCountReached { value: u32, max: u32 },
 
     #[error(
         code = 232,
-        msg = "Invalid leverage, leverage {value} > max leverage {max} or equal to 0"
+        msg = "Invalid leverage, leverage {value} > max leverage {max} or equal to 0",
+        category = "validation"
     )]
     InvalidLeverage { value: u32, max: u32 },
-    #[error(code = 233, msg = "Invalid Socialized Loss Open Interest")]
+    #[error(
+        code = 233,
+        msg = "Invalid Socialized Loss Open Interest",
+        category = "system"
+    )]
     InvalidSocializedLossOpenInterest,
 
-    #[error(code = 234, msg = "Impossible to close perp position")]
+    #[error(
+        code = 234,
+        msg = "Impossible to close perp position",
+        category = "risk"
+    )]
     ImpossibleToClosePerpPosition,
 
     #[error(
         code = 235,
-        msg = "Too early to withdraw fees, current time is {current_time} < allowed time {limit_time}"
+        msg = "Too early to withdraw fees, current time is {current_time} < allowed time {limit_time}",
+        category = "state",
+        retryable = true
     )]
     TooEarlyToWithdrawFees { limit_time: u32, current_time: u32 },
 
-    #[error(code = 236, msg = "Fees withdrawal is too large")]
+    #[error(code = 236, msg = "Fees withdrawal is too large", category = "risk")]
     FeesWithdrawalIsTooLarge { value: i64 },
 
-    #[error(code = 237, msg = "Invalid oracle feed")]
+    #[error(code = 237, msg = "Invalid oracle feed", category = "validation")]
     InvalidOracleFeed {
+        #[error(display)]
         expected_address: Pubkey,
+        #[error(display)]
         actual_address: Pubkey,
     },
     #[error(
         code = 238,
-        msg = "Invalid Ref discount {discount}, expected to be in range {min}..{max}"
+        msg = "Invalid Ref discount {discount}, expected to be in range {min}..{max}",
+        category = "validation"
     )]
     InvalidRefDiscount { discount: f64, min: f64, max: f64 },
 
     #[error(
         code = 238,
-        msg = "Invalid Ref ratio {ratio}, expected to be in range {min}..{max}"
+        alias = "same ref parameter range check",
+        msg = "Invalid Ref ratio {ratio}, expected to be in range {min}..{max}",
+        category = "validation"
     )]
     InvalidRefRatio { ratio: f64, min: f64, max: f64 },
 
-    #[error(code = 239, msg = "Ref program is inactive")]
+    #[error(code = 239, msg = "Ref program is inactive", category = "state")]
     RefProgramInactive,
 
     #[error(
         code = 240,
-        msg = "Invalid ref link ID {ref_id}, acceptable ids: {first_ref_id}, {second_ref_id}"
+        msg = "Invalid ref link ID {ref_id}, acceptable ids: {first_ref_id}, {second_ref_id}",
+        category = "validation"
     )]
     InvalidRefLinkId {
         ref_id: u32,
@@ -470,7 +788,8 @@ pub enum DeriverseErrorKind {
 
     #[error(
         code = 241,
-        msg = "Ref link with id {ref_id} is expired, current time is {current_time} > expiration time {expiration_time}"
+        msg = "Ref link with id {ref_id} is expired, current time is {current_time} > expiration time {expiration_time}",
+        category = "state"
     )]
     RefLinkExpired {
         ref_id: u32,
@@ -478,183 +797,296 @@ pub enum DeriverseErrorKind {
         current_time: u32,
     },
 
-    #[error(code = 242, msg = "Invalid referral client primary account address")]
+    #[error(
+        code = 242,
+        msg = "Invalid referral client primary account address",
+        category = "validation"
+    )]
     InvalidRefAddress {
+        #[error(display)]
         expected_address: Pubkey,
+        #[error(display)]
         actual_address: Pubkey,
     },
 
-    #[error(code = 243, msg = "Operation Rejected")]
+    #[error(code = 243, msg = "Operation Rejected", category = "permission")]
     OperationRejected,
 
-    #[error(code = 244, msg = "Memory map creation or general error")]
+    #[error(
+        code = 244,
+        msg = "Memory map creation or general error",
+        category = "system",
+        retryable = true
+    )]
     MemoryMapFailed,
 
-    #[error(code = 245, msg = "Memory map deallocation error")]
+    #[error(code = 245, msg = "Memory map deallocation error", category = "system")]
     MemoryMapFreeFailed,
 
     #[error(
         code = 247,
-        msg = "Invalid account tag in account, expected {{*expected_account_type as u32}} != actual {actual_tag}"
+        msg = "Invalid account tag in account, expected {{*expected_account_type as u32}} != actual {actual_tag}",
+        category = "validation"
     )]
     InvalidAccountTag {
+        #[error(display)]
         expected_account_type: AccountType,
         actual_tag: u32,
+        #[error(display)]
         address: Pubkey,
     },
     #[error(
         code = 249,
-        msg = "Invalid version in {account_type:?}, expected {expected} != actual {actual}"
+        msg = "Invalid version in {account_type:?}, expected {expected} != actual {actual}",
+        category = "validation"
     )]
     InvalidVersion {
+        #[error(display)]
         address: Pubkey,
+        #[error(display)]
         account_type: AccountType,
         expected: u32,
         actual: u32,
     },
-    #[error(code = 250, msg = "Invalid data alignment in {address}")]
-    InvalidDataAlignment { address: Pubkey },
+    #[error(
+        code = 250,
+        msg = "Invalid data alignment in {address}",
+        category = "validation"
+    )]
+    InvalidDataAlignment {
+        #[error(display)]
+        address: Pubkey,
+    },
 
     #[error(
         code = 251,
-        msg = "Invalid amount of provided accounts, expected {expected} != actual {actual}"
+        msg = "Invalid amount of provided accounts, expected {expected} != actual {actual}",
+        category = "validation"
     )]
     InvalidAccountsAmount { expected: u32, actual: u32 },
     #[error(
         code = 252,
-        msg = "wSOL minting at legacy solana_native address is not supported"
+        msg = "wSOL minting at legacy solana_native address is not supported",
+        category = "validation"
     )]
     LegacyNativeMintNotSupported,
 
-    #[error(code = 253, msg = "Identical tokens are not allowed in trading pair")]
+    #[error(
+        code = 253,
+        msg = "Identical tokens are not allowed in trading pair",
+        category = "validation"
+    )]
     IdenticalTokensInPair { token_id: u32 },
     #[error(
         code = 255,
-        msg = "wSOL minting at solana_native address is not supported"
+        msg = "wSOL minting at solana_native address is not supported",
+        category = "validation"
     )]
     Token2022NativeMintNotSupported,
 
     #[error(
         code = 256,
-        msg = "Invalid accounts address for {account_type} account"
+        msg = "Invalid accounts address for {account_type} account",
+        category = "validation"
     )]
     InvalidAccountAddress {
+        #[error(display)]
         expected_address: Pubkey,
+        #[error(display)]
         actual_address: Pubkey,
+        #[error(display)]
         account_type: AccountType,
     },
-    #[error(code = 212, msg = "Candle with tag {tag} was not found")]
+    #[error(
+        code = 212,
+        known_collision,
+        msg = "Candle with tag {tag} was not found",
+        category = "state"
+    )]
     CandleWasNotFound { tag: u32 },
 
     #[error(
         code = 250,
-        msg = "Invalid accounts size in {account_type} account, actual {actual} < expected {expected}"
+        alias = "malformed account data",
+        msg = "Invalid accounts size in {account_type} account, actual {actual} < expected {expected}",
+        category = "validation"
     )]
     InvalidAccountSize {
+        #[error(display)]
         address: Pubkey,
+        #[error(display)]
         account_type: AccountType,
         expected: usize,
         actual: usize,
     },
-    #[error(code = 246, msg = "Failed to upgrade access manager to writable")]
+    #[error(
+        code = 246,
+        known_collision,
+        msg = "Failed to upgrade access manager to writable",
+        category = "system"
+    )]
     FailedToUpgrade,
 
     #[error(
         code = 258,
-        msg = "Invalid candles amount, expected candle on index {index} exist while length is {len}"
+        msg = "Invalid candles amount, expected candle on index {index} exist while length is {len}",
+        category = "validation"
     )]
     InvalidCandlesAmount { index: usize, len: usize },
 
-    #[error(code = 110, msg = "Invalid wallet address")]
+    #[error(code = 110, msg = "Invalid wallet address", category = "validation")]
     #[serde(rename = "110")]
     InvalidWalletAddress {
+        #[error(display)]
         address: Pubkey,
+        #[error(display)]
         wallet_address: Pubkey,
+        #[error(display)]
         actual_address: Pubkey,
     },
-    #[error(code = 224, msg = "Invalid candles context")]
+    #[error(
+        code = 224,
+        known_collision,
+        msg = "Invalid candles context",
+        category = "validation"
+    )]
     InvalidCandlesContext,
 
-    #[error(code = 212, msg = "Unsupported account tag {tag}")]
+    #[error(
+        code = 212,
+        known_collision,
+        msg = "Unsupported account tag {tag}",
+        category = "validation"
+    )]
     UnsupportedAccountTag { tag: u32 },
 
     #[error(
         code = 239,
-        msg = "Self-referral is not allowed, client can not be referral for himself"
+        known_collision,
+        msg = "Self-referral is not allowed, client can not be referral for himself",
+        category = "validation"
     )]
     SelfRefNotAllowed { client_primary_addr: Pubkey },
 
-    #[error(code = 250, msg = "Offset {offset} is out of bound for data len {len}")]
+    #[error(
+        code = 250,
+        alias = "malformed account data",
+        msg = "Offset {offset} is out of bound for data len {len}",
+        category = "validation"
+    )]
     OffsetOutOfBounds {
+        #[error(display)]
         address: Pubkey,
         offset: usize,
         len: usize,
     },
 
-    #[error(code = 284, msg = "Invalid token owner for {token_id} token")]
+    #[error(
+        code = 284,
+        msg = "Invalid token owner for {token_id} token",
+        category = "validation"
+    )]
     InvalidTokenOwner {
         token_id: u32,
+        #[error(display)]
         address: Pubkey,
+        #[error(display)]
         expected_address: Pubkey,
+        #[error(display)]
         actual_address: Pubkey,
     },
 
-    #[error(code = 280, msg = "Different token ids, {id_left} != {id_right}")]
+    #[error(
+        code = 280,
+        msg = "Different token ids, {id_left} != {id_right}",
+        category = "validation"
+    )]
     DifferentTokenIds { id_left: u32, id_right: u32 },
 
-    #[error(code = 281, msg = "Perp clients count must not be 0")]
+    #[error(
+        code = 281,
+        msg = "Perp clients count must not be 0",
+        category = "validation"
+    )]
     InvalidPerpClientsCount,
 
     #[error(
         code = 282,
-        msg = "Invalid supply {supply}, supply difference to MAX SUPPLY: {supply_difference}"
+        msg = "Invalid supply {supply}, supply difference to MAX SUPPLY: {supply_difference}",
+        category = "validation"
     )]
     InvalidSupply { supply: u32, supply_difference: u32 },
 
-    #[error(code = 283, msg = "Perp was already allocated")]
+    #[error(code = 283, msg = "Perp was already allocated", category = "state")]
     PerpAlreadyAllocated,
 
-    #[error(code = 284, msg = "Traded amount ({amount}) is too small")]
+    #[error(
+        code = 284,
+        known_collision,
+        msg = "Traded amount ({amount}) is too small",
+        category = "risk"
+    )]
     TradeIsTooSmall { amount: u32 },
 
-    #[error(code = 285, msg = "Client primary account must be some")]
+    #[error(
+        code = 285,
+        msg = "Client primary account must be some",
+        category = "validation"
+    )]
     ClientPrimaryAccountMustBeSome,
 
-    #[error(code = 286, msg = "Must be in Private Mode")]
+    #[error(code = 286, msg = "Must be in Private Mode", category = "permission")]
     MustBeInPrivateMode,
 
     #[error(
         code = 287,
-        msg = "Non admin attemp of pair with DRVS token creation from {client_address}"
+        msg = "Non admin attemp of pair with DRVS token creation from {client_address}",
+        category = "permission"
     )]
     NonAdminDrvsInstr {
+        #[error(display)]
         client_address: Pubkey,
         asset_token_id: u32,
         crncy_token_id: u32,
     },
 
-    #[error(code = 288, msg = "{client_address} is not in private queue")]
-    ClientIsNotInPrivate { client_address: Pubkey },
+    #[error(
+        code = 288,
+        msg = "{client_address} is not in private queue",
+        category = "permission"
+    )]
+    ClientIsNotInPrivate {
+        #[error(display)]
+        client_address: Pubkey,
+    },
 
     #[error(
         code = 289,
-        msg = "Invalid tokens decimals amount: {decs_count}, expected to be in range {min}..={max}"
+        msg = "Invalid tokens decimals amount: {decs_count}, expected to be in range {min}..={max}",
+        category = "validation"
     )]
     InvalidDecsCount {
         decs_count: u32,
         min: u32,
         max: u32,
+        #[error(display)]
         token_address: Pubkey,
     },
     #[error(
         code = 290,
-        msg = "Clients wallet is already in private queue on index {index}"
+        msg = "Clients wallet is already in private queue on index {index}",
+        category = "state"
     )]
-    WalletIsInPrivateQueue { index: u32, wallet_address: Pubkey },
+    WalletIsInPrivateQueue {
+        index: u32,
+        #[error(display)]
+        wallet_address: Pubkey,
+    },
 
     #[error(
         code = 291,
-        msg = "Invalid new expiration time for {program_name}, new time {new_time} < old_time {old_time}"
+        msg = "Invalid new expiration time for {program_name}, new time {new_time} < old_time {old_time}",
+        category = "validation"
     )]
     InvalidNewExpirationTime {
         program_name: String,
@@ -664,7 +1096,8 @@ pub enum DeriverseErrorKind {
 
     #[error(
         code = 292,
-        msg = "Provided variance is invalid, variance {variance} must be in {min_variance}..{max_variance}"
+        msg = "Provided variance is invalid, variance {variance} must be in {min_variance}..{max_variance}",
+        category = "validation"
     )]
     InvalidVariance {
         variance: f64,
@@ -672,48 +1105,68 @@ pub enum DeriverseErrorKind {
         max_variance: f64,
     },
 
-    #[error(code = 293, msg = "Airdrop amount must be > 0")]
-    InivalidAirdrop { wallet_address: Pubkey, ratio: f64 },
+    #[error(
+        code = 293,
+        msg = "Airdrop amount must be > 0",
+        category = "validation"
+    )]
+    InivalidAirdrop {
+        #[error(display)]
+        wallet_address: Pubkey,
+        ratio: f64,
+    },
 
     #[error(
         code = 294,
-        msg = "Airdrop authority wasn't initialised correctly. Expected {expected_address} != Actual {actual_address}"
+        msg = "Airdrop authority wasn't initialised correctly. Expected {expected_address} != Actual {actual_address}",
+        category = "permission"
     )]
     InvalidAirdropAuthority {
+        #[error(display)]
         expected_address: Pubkey,
+        #[error(display)]
         actual_address: Pubkey,
     },
 
     #[error(
         code = 295,
-        msg = "Invalid Spl Token Program Id, expected: {expected}, actual: {actual}"
+        msg = "Invalid Spl Token Program Id, expected: {expected}, actual: {actual}",
+        category = "validation"
     )]
     InvalidSplTokenProgramId { expected: Pubkey, actual: Pubkey },
 
     #[error(
         code = 295,
-        msg = "Failed to swap with give price {price} on side {side}"
+        known_collision,
+        msg = "Failed to swap with give price {price} on side {side}",
+        category = "risk",
+        retryable = true
     )]
     FailedToSwap { price: i64, side: OrderSide },
 
     #[error(
         code = 297,
-        msg = "Token with id {token_id} can't be a base crncy. Reason: {reason}"
+        msg = "Token with id {token_id} can't be a base crncy. Reason: {reason}",
+        category = "validation"
     )]
     InvalidBaseCrncy { token_id: u32, reason: String },
 
     #[error(
         code = 298,
-        msg = "Private mode authority wasn't initialised correctly. Expected {expected_address} != Actual {actual_address}"
+        msg = "Private mode authority wasn't initialised correctly. Expected {expected_address} != Actual {actual_address}",
+        category = "permission"
     )]
     InvalidPrivateModeAuthority {
+        #[error(display)]
         expected_address: Pubkey,
+        #[error(display)]
         actual_address: Pubkey,
     },
 
     #[error {
         code = 299,
-        msg = "Max clients orders limit reached. For current client on {side} side max orders amount is {max_clients_orders}"
+        msg = "Max clients orders limit reached. For current client on {side} side max orders amount is {max_clients_orders}",
+        category = "state"
     }]
     MaxClientsOrderLimitReached {
         side: OrderSide,
@@ -722,13 +1175,16 @@ pub enum DeriverseErrorKind {
 
     #[error(
         code = 300,
-        msg = "Slippage bounds exceeded. Price: {price}, Bounds type: {bound_price}"
+        msg = "Slippage bounds exceeded. Price: {price}, Bounds type: {bound_price}",
+        category = "risk",
+        retryable = true
     )]
     PriceSlippageExceeded { price: i64, bound_price: i64 },
 
     #[error(
         code = 301,
-        msg = "Insuffecient LP tokens balance. Tokens after operation {final_tokens} < min amount {min_amount}"
+        msg = "Insuffecient LP tokens balance. Tokens after operation {final_tokens} < min amount {min_amount}",
+        category = "risk"
     )]
     InsuffecientLpTokensBalance {
         lp_tokens: i64,
@@ -739,7 +1195,8 @@ pub enum DeriverseErrorKind {
 
     #[error(
         code = 302,
-        msg = "Insuffecient pool supply. Pool supply after operation {final_ps} < min amount {min_amount}"
+        msg = "Insuffecient pool supply. Pool supply after operation {final_ps} < min amount {min_amount}",
+        category = "risk"
     )]
     InsuffecientPoolSupply {
         ps: i64,
@@ -750,61 +1207,101 @@ pub enum DeriverseErrorKind {
 
     #[error(
         code = 303,
-        msg = "System fault. In case of empty pool users lp balance must be 0"
+        msg = "System fault. In case of empty pool users lp balance must be 0",
+        category = "system"
     )]
     SystemPoolFault { ps: i64, lp_tokens: i64 },
 
     #[error(
         code = 304,
-        msg = "Max instruments limit on the platform reached: {max_instr_amount}"
+        msg = "Max instruments limit on the platform reached: {max_instr_amount}",
+        category = "state"
     )]
     MaxInstrumentLimitReached { max_instr_amount: u32 },
 
-    #[error(code = 305, msg = "Invalid order type {order_type}")]
+    #[error(
+        code = 305,
+        msg = "Invalid order type {order_type}",
+        category = "validation"
+    )]
     InvalidOrderType {
         order_type: OrderType,
         order_type_raw: u8,
     },
 
-    #[error(code = 306, msg = "Invalid denominator {denominator}, must be > 0")]
+    #[error(
+        code = 306,
+        msg = "Invalid denominator {denominator}, must be > 0",
+        category = "validation"
+    )]
     InvalidDenominator { denominator: f64 },
 
     #[error(
         code = 307,
-        msg = "Invalid base crncy id {base_crncy_id} was not found"
+        msg = "Invalid base crncy id {base_crncy_id} was not found",
+        category = "validation"
     )]
     InvalidBaseCrncyId { base_crncy_id: u32 },
 
-    #[error(code = 308, msg = "Invalid fee rate, {fee_rate} < 0")]
+    #[error(
+        code = 308,
+        msg = "Invalid fee rate, {fee_rate} < 0",
+        category = "validation"
+    )]
     InvalidFeeValue { fee_rate: f64 },
 
-    #[error(code = 309, msg = "No vote was found to change")]
+    #[error(code = 309, msg = "No vote was found to change", category = "state")]
     NoVoteToChange { voting_counter: u32 },
 
-    #[error(code = 310, msg = "Invalid voting choice {choice}")]
+    #[error(
+        code = 310,
+        msg = "Invalid voting choice {choice}",
+        category = "validation"
+    )]
     InvalidVotingChoice { voting_counter: u32, choice: u8 },
 
-    #[error(code = 311, msg = "Referral id already assigned {ref_id}")]
+    #[error(
+        code = 311,
+        msg = "Referral id already assigned {ref_id}",
+        category = "state"
+    )]
     ReferralIdAlreadyAssigned { ref_id: u32 },
 
-    #[error(code = 312, msg = "Invalid ref id value: {ref_id}")]
+    #[error(
+        code = 312,
+        msg = "Invalid ref id value: {ref_id}",
+        category = "validation"
+    )]
     InvalidRefIdValue { ref_id: u32 },
 
-    #[error(code = 313, msg = "Attempted to create a referral on itself")]
+    #[error(
+        code = 313,
+        msg = "Attempted to create a referral on itself",
+        category = "validation"
+    )]
     SelfRefLink,
 
     #[error(
         code = 314,
-        msg = "Client {wallet_address} already has a registered account"
+        msg = "Client {wallet_address} already has a registered account",
+        category = "state"
     )]
-    AttemptedToAddExistingClient { wallet_address: Pubkey },
+    AttemptedToAddExistingClient {
+        #[error(display)]
+        wallet_address: Pubkey,
+    },
 
-    #[error(code = 315, msg = "Invalid edge price {price}")]
+    #[error(
+        code = 315,
+        msg = "Invalid edge price {price}",
+        category = "validation"
+    )]
     InvalidEdgePrice { price: i64 },
 
     #[error(
         code = 316,
-        msg = "Incorrect Vm Mode status flag {flag:?}. Expected to be {expected}, actual {actual}"
+        msg = "Incorrect Vm Mode status flag {flag:?}. Expected to be {expected}, actual {actual}",
+        category = "state"
     )]
     IncorrectVmModeStatus {
         flag: VmFlag,
@@ -812,16 +1309,20 @@ pub enum DeriverseErrorKind {
         actual: bool,
     },
 
-    #[error(code = 317, msg = "Invalid vm wallet address")]
+    #[error(code = 317, msg = "Invalid vm wallet address", category = "validation")]
     InvalidVmWalletAddress {
+        #[error(display)]
         address: Pubkey,
+        #[error(display)]
         actual_address: Pubkey,
+        #[error(display)]
         vm_wallet_address: Pubkey,
     },
 
     #[error(
         code = 318,
-        msg = "No permission for instrument {instr_id} and trading section {trading_section}"
+        msg = "No permission for instrument {instr_id} and trading section {trading_section}",
+        category = "permission"
     )]
     InstrumentPermissionDenied {
         instr_id: u32,
@@ -830,7 +1331,8 @@ pub enum DeriverseErrorKind {
 
     #[error(
         code = 319,
-        msg = "Instruction {name}:{instruction_number} is restricted during vm mode"
+        msg = "Instruction {name}:{instruction_number} is restricted during vm mode",
+        category = "permission"
     )]
     RestrictedInstructionDuringVmMode {
         name: String,
@@ -839,25 +1341,33 @@ pub enum DeriverseErrorKind {
 
     #[error(
         code = 320,
-        msg = "Slippage bounds exceeded. Out amount: {amount}, Bounds amount: {bound_amount}"
+        msg = "Slippage bounds exceeded. Out amount: {amount}, Bounds amount: {bound_amount}",
+        category = "risk",
+        retryable = true
     )]
     OutAmountSlippageExceeded { bound_amount: i64, amount: i64 },
 
-    #[error(code = 321, msg = "Cannot assign Vm Authority to self")]
+    #[error(
+        code = 321,
+        msg = "Cannot assign Vm Authority to self",
+        category = "permission"
+    )]
     InvalidVmAuthorityAssignment,
 
-    #[error(code = 322, msg = "Allocation is forbidden")]
+    #[error(code = 322, msg = "Allocation is forbidden", category = "permission")]
     AllocationIsForbidden,
 
     #[error(
         code = 323,
-        msg = "Quotes params crossed, min ask price: {min_ask}, max bid price {max_bid}"
+        msg = "Quotes params crossed, min ask price: {min_ask}, max bid price {max_bid}",
+        category = "validation"
     )]
     CrossQuotesParams { min_ask: i64, max_bid: i64 },
 
     #[error(
         code = 324,
-        msg = "Invalid quote orders amount mask amount: {mask_amount}, orders amount: {orders_amount}"
+        msg = "Invalid quote orders amount mask amount: {mask_amount}, orders amount: {orders_amount}",
+        category = "validation"
     )]
     InvalidQuoteOrdersAmount {
         mask_amount: u32,
@@ -866,57 +1376,98 @@ pub enum DeriverseErrorKind {
 
     #[error(
         code = 325,
-        msg = "maker_only is incompatible with IOC or Market orders"
+        msg = "maker_only is incompatible with IOC or Market orders",
+        category = "validation"
     )]
     MakerOnlyConflict,
 
-    #[error(code = 326, msg = "Invalid operation for similar assets market")]
+    #[error(
+        code = 326,
+        msg = "Invalid operation for similar assets market",
+        category = "validation"
+    )]
     InvalidOperationSimilarAssets,
 
-    #[error(code = 327, msg = "Similar Assets Market is not active")]
+    #[error(
+        code = 327,
+        msg = "Similar Assets Market is not active",
+        category = "state"
+    )]
     SAMIsNotActive,
 
-    #[error(code = 327, msg = "Invalid operation for active perp")]
+    #[error(
+        code = 327,
+        known_collision,
+        msg = "Invalid operation for active perp",
+        category = "state"
+    )]
     InvalidOperationForActivePerp,
 
-    #[error(code = 328, msg = "Impossible to suspend instrument")]
+    #[error(
+        code = 328,
+        msg = "Impossible to suspend instrument",
+        category = "state"
+    )]
     ImpossibleToSuspend,
 
     #[error(
         code = 329,
-        msg = "Can not create an instrument with {reason} for {mint}"
+        msg = "Can not create an instrument with {reason} for {mint}",
+        category = "state"
     )]
     ImpossibleToCreateInstrument {
         reason: ForbiddenTokensParams,
         mint: Pubkey,
     },
 
-    #[error(code = 330, msg = "Pool deposit is disabled for ZeroFees market")]
+    #[error(
+        code = 330,
+        msg = "Pool deposit is disabled for ZeroFees market",
+        category = "state"
+    )]
     PoolDepositDisabled { mask: u32 },
 
     #[error(
         code = 330,
-        msg = "Can not set instr flag {flag} without {required_flag} flag up"
+        known_collision,
+        msg = "Can not set instr flag {flag} without {required_flag} flag up",
+        category = "state"
     )]
     CanNotSetInstrFlag {
         flag: InstrFlag,
         required_flag: InstrFlag,
     },
 
-    #[error(code = 331, msg = "Impossible to create SAM market with SAMCrncy flag")]
+    #[error(
+        code = 331,
+        msg = "Impossible to create SAM market with SAMCrncy flag",
+        category = "validation"
+    )]
     ImpossibleToCreateSAMWithSAMCrncy,
 
     #[error(
         code = 332,
-        msg = "Can not set instr flag {flag} as {up_flag} flag is up"
+        msg = "Can not set instr flag {flag} as {up_flag} flag is up",
+        category = "state"
     )]
     ConflictInstrFlags { flag: InstrFlag, up_flag: InstrFlag },
 
     #[error(
         code = 333,
-        msg = "Instrument is suspended, new orders can not be added"
+        msg = "Instrument is suspended, new orders can not be added",
+        category = "state"
     )]
     SuspendedInstrument,
+
+    #[error(code = 335, msg = "Division by zero", category = "validation")]
+    DivisionByZero,
+
+    #[error(
+        code = 336,
+        msg = "Unknown order type {order_type_raw}",
+        category = "validation"
+    )]
+    UnknownOrderType { order_type_raw: u8 },
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -1048,4 +1599,196 @@ mod tests {
         let err2 = DeriverseErrorKind::InvalidOperatorAccount { address };
         assert_eq!(err1, err2);
     }
+
+    #[test]
+    fn test_error_table() {
+        assert_eq!(
+            DeriverseErrorKind::entries_with_code(246).next(),
+            Some(&(246, "MustBeReadOnly", "{address} must be read only"))
+        );
+        assert_eq!(
+            DeriverseErrorKind::from_code(137).map(|entry| entry.1),
+            Some("AllocatorFailed")
+        );
+        assert_eq!(DeriverseErrorKind::from_code(212), None);
+        assert_eq!(
+            DeriverseErrorKind::entries_with_code(212)
+                .map(|entry| entry.1)
+                .collect::<Vec<_>>(),
+            [
+                "TokenWasNotFound",
+                "AssetNotFound",
+                "CandleWasNotFound",
+                "UnsupportedAccountTag"
+            ]
+        );
+        assert_eq!(
+            DeriverseErrorKind::KNOWN_COLLISIONS,
+            [212, 224, 239, 246, 284, 295, 327, 330]
+        );
+        assert_eq!(DeriverseErrorKind::from_code(1), None);
+
+        let error = DeriverseErrorKind::MustBeWritable {
+            address: Pubkey::new_unique(),
+        };
+        assert_eq!(error.name(), "MustBeWritable");
+        assert!(ERROR_TABLE.contains(&(error.code(), error.name(), "{address} must be writable")));
+
+        for (code, name, _) in ERROR_TABLE {
+            assert_eq!(
+                DeriverseErrorKind::from_code(*code).is_some(),
+                !DeriverseErrorKind::KNOWN_COLLISIONS.contains(code),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_log_line() {
+        let error = DeriverseError::new(
+            DeriverseErrorKind::InvalidAccountTag {
+                expected_account_type: AccountType::Community,
+                actual_tag: 7,
+                address: Pubkey::new_unique(),
+            },
+            file!(),
+            line!(),
+        );
+        let line = format!("Program log: {}", error.to_json());
+        assert_eq!(
+            ParsedDeriverseError::from_log_line(&line),
+            Some(ParsedDeriverseError {
+                error: error.error,
+                location: ParsedErrorLocation {
+                    file: error.location.file.to_string(),
+                    line: error.location.line,
+                },
+            })
+        );
+
+        // Aliases are told apart by the message
+        let writable = DeriverseErrorKind::MustBeWritable {
+            address: Pubkey::new_unique(),
+        };
+        assert_eq!(
+            DeriverseErrorKind::from_json(&writable.to_json()),
+            Some(writable)
+        );
+
+        let system = DeriverseErrorKind::SystemError {
+            error: ProgramError::InvalidAccountData,
+        };
+        assert_eq!(
+            DeriverseErrorKind::from_json(&system.to_json()),
+            Some(system)
+        );
+
+        assert_eq!(
+            ParsedDeriverseError::from_log_line(
+                &DeriverseErrorKind::OperationRejected.to_json().to_string()
+            ),
+            None
+        );
+        assert_eq!(
+            DeriverseErrorKind::from_json(&serde_json::json!({ "code": 1 })),
+            None
+        );
+    }
+
+    #[test]
+    fn test_category_and_retryable() {
+        let slippage = DeriverseErrorKind::PriceSlippageExceeded {
+            price: 100,
+            bound_price: 90,
+        };
+        assert_eq!(slippage.category(), ErrorCategory::Risk);
+        assert!(slippage.is_retryable());
+
+        let json = slippage.to_json();
+        assert_eq!(json["category"], "risk");
+        assert_eq!(json["retryable"], true);
+
+        assert!(DeriverseErrorKind::NoTradeIOC.is_retryable());
+        assert!(DeriverseErrorKind::MemoryMapFailed.is_retryable());
+        assert_eq!(
+            DeriverseErrorKind::MemoryMapFailed.category(),
+            ErrorCategory::System
+        );
+        assert!(!DeriverseErrorKind::OperationRejected.is_retryable());
+        assert_eq!(
+            DeriverseErrorKind::MustBeSigner {
+                address: Pubkey::new_unique()
+            }
+            .category(),
+            ErrorCategory::Permission
+        );
+    }
+
+    mod hex {
+        pub(super) fn to_json(value: &u32) -> serde_json::Value {
+            serde_json::json!(format!("{:#x}", value))
+        }
+
+        pub(super) fn from_json(value: &serde_json::Value) -> Option<u32> {
+            u32::from_str_radix(value.as_str()?.strip_prefix("0x")?, 16).ok()
+        }
+    }
+
+    #[derive(Debug, DrvError, PartialEq)]
+    enum TestError {
+        #[error(code = 1, msg = "Client {0} with {1}", category = "state")]
+        Tuple(u32, #[error(display)] Pubkey),
+
+        #[error(code = 2, msg = "Mask {mask:?}", category = "system", retryable = true)]
+        Fields {
+            #[error(with = hex)]
+            mask: u32,
+            #[error(skip)]
+            hint: u64,
+        },
+    }
+
+    #[test]
+    fn test_field_rendering() {
+        let address = Pubkey::new_unique();
+        let tuple = TestError::Tuple(7, address);
+        assert_eq!(tuple.to_string(), format!("Client 7 with {}", address));
+
+        let json = tuple.to_json();
+        assert_eq!(json["0"], 7);
+        assert_eq!(json["1"], address.to_string());
+        assert_eq!(TestError::from_json(&json), Some(tuple));
+        assert_eq!(
+            TestError::from_code(1),
+            Some(&(1, "Tuple", "Client {0} with {1}"))
+        );
+
+        let fields = TestError::Fields { mask: 16, hint: 3 };
+        assert_eq!(fields.to_string(), "Mask 16");
+
+        let json = fields.to_json();
+        assert_eq!(json["mask"], "0x10");
+        assert!(json.get("hint").is_none());
+        assert_eq!(
+            TestError::from_json(&json),
+            Some(TestError::Fields { mask: 16, hint: 0 })
+        );
+    }
+
+    mod unscoped {
+        #[derive(Debug, drv_errors_derive::DrvError)]
+        pub(super) enum UnscopedError {
+            #[error(code = 1, msg = "Unscoped", category = "risk")]
+            Unscoped,
+        }
+    }
+
+    #[test]
+    fn test_category_path() {
+        assert_eq!(
+            unscoped::UnscopedError::Unscoped.category(),
+            ErrorCategory::Risk
+        );
+    }
 }
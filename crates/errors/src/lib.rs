@@ -1 +1,4 @@
+// Generated `DrvError` impls name `::drv_errors` paths, this crate included
+extern crate self as drv_errors;
+
 pub mod errors;
@@ -17,7 +17,7 @@ use crate::{
 pub mod candles {
 
     use super::*;
-    #[derive(Clone, Copy)]
+    #[derive(Clone, Copy, Debug)]
     pub struct CandleParams {
         pub tag: u32,
         pub capacity: u32,
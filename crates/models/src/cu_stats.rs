@@ -0,0 +1,193 @@
+//! # Compute units stats module
+//! - Off-chain parser of `drv_macros::cu_stats` scopes in transaction logs
+//! - Scope entry logs `cu:>label` followed by remaining units, scope exit
+//!   logs remaining units followed by `cu:<label`
+//! - Remaining units are logged by the runtime as
+//!   `Program consumption: N units remaining`
+//! - Scopes still open when logs end (failed transaction) are closed with the
+//!   last logged remaining units
+
+use std::collections::BTreeMap;
+
+/// Log prefix of a scope entry, checked against `drv_macros::cu_stats` by its tests
+pub const CU_ENTER_PREFIX: &str = "cu:>";
+/// Log prefix of a scope exit, checked against `drv_macros::cu_stats` by its tests
+pub const CU_EXIT_PREFIX: &str = "cu:<";
+
+const LOG_PREFIX: &str = "Program log: ";
+const CONSUMPTION_PREFIX: &str = "Program consumption: ";
+const CONSUMPTION_SUFFIX: &str = " units remaining";
+
+/// Compute Units Node
+///
+/// One execution of a labelled scope
+///
+/// 1. **`label`** - Function name or label given to `cu_stats`
+/// 2. **`consumed`** - Units consumed including nested scopes
+/// 3. **`children`** - Nested scopes in execution order
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CuNode {
+    pub label: String,
+    pub consumed: u64,
+    pub children: Vec<Self>,
+}
+
+impl CuNode {
+    /// Units consumed by the scope itself, without nested scopes
+    pub fn self_consumed(&self) -> u64 {
+        self.consumed
+            .saturating_sub(self.children.iter().map(|child| child.consumed).sum())
+    }
+}
+
+/// Compute Units Tree
+///
+/// Scopes of one transaction, top level scopes in execution order
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CuTree {
+    pub roots: Vec<CuNode>,
+}
+
+/// Open scope with remaining units at entry, unknown until the runtime logs them
+struct OpenScope {
+    node: CuNode,
+    remaining: Option<u64>,
+}
+
+impl OpenScope {
+    fn close(mut self, remaining: Option<u64>) -> CuNode {
+        self.node.consumed = match (self.remaining, remaining) {
+            (Some(start), Some(end)) => start.saturating_sub(end),
+            _ => 0,
+        };
+        self.node
+    }
+}
+
+fn consumption(line: &str) -> Option<u64> {
+    line.strip_prefix(CONSUMPTION_PREFIX)?
+        .strip_suffix(CONSUMPTION_SUFFIX)?
+        .parse()
+        .ok()
+}
+
+impl CuTree {
+    /// Builds the tree from transaction log lines, lines unrelated to
+    /// `cu_stats` are skipped
+    pub fn parse<'a>(logs: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut tree = Self::default();
+        let mut stack: Vec<OpenScope> = Vec::new();
+        let mut remaining = None;
+
+        for line in logs {
+            let line = line.trim();
+
+            if let Some(units) = consumption(line) {
+                remaining = Some(units);
+                if let Some(scope) = stack.last_mut() {
+                    scope.remaining.get_or_insert(units);
+                }
+                continue;
+            }
+
+            let message = line.strip_prefix(LOG_PREFIX).unwrap_or(line);
+            if let Some(label) = message.strip_prefix(CU_ENTER_PREFIX) {
+                stack.push(OpenScope {
+                    node: CuNode {
+                        label: label.to_string(),
+                        ..CuNode::default()
+                    },
+                    remaining: None,
+                });
+            } else if let Some(label) = message.strip_prefix(CU_EXIT_PREFIX) {
+                // Unmatched exits are skipped, scopes left open inside the
+                // exited one are closed with it
+                if let Some(position) = stack.iter().rposition(|scope| scope.node.label == label) {
+                    while stack.len() > position {
+                        tree.close(&mut stack, remaining);
+                    }
+                }
+            }
+        }
+
+        while !stack.is_empty() {
+            tree.close(&mut stack, remaining);
+        }
+
+        tree
+    }
+
+    fn close(&mut self, stack: &mut Vec<OpenScope>, remaining: Option<u64>) {
+        if let Some(scope) = stack.pop() {
+            let node = scope.close(remaining);
+            match stack.last_mut() {
+                Some(parent) => parent.node.children.push(node),
+                None => self.roots.push(node),
+            }
+        }
+    }
+
+    /// Execution count and total consumed units per label
+    pub fn totals(&self) -> BTreeMap<&str, (u32, u64)> {
+        let mut totals = BTreeMap::new();
+        let mut nodes = self.roots.iter().collect::<Vec<_>>();
+
+        while let Some(node) = nodes.pop() {
+            let (count, consumed) = totals.entry(node.label.as_str()).or_insert((0, 0));
+            *count += 1;
+            *consumed += node.consumed;
+            nodes.extend(&node.children);
+        }
+
+        totals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cu_tree() {
+        let logs = [
+            "Program Drv111 invoke [1]",
+            "Program log: cu:>new_perp_order",
+            "Program consumption: 199000 units remaining",
+            "Program log: cu:>match_orders",
+            "Program consumption: 198000 units remaining",
+            "Program consumption: 190000 units remaining",
+            "Program log: cu:<match_orders",
+            "Program log: cu:>match_orders",
+            "Program consumption: 189000 units remaining",
+            "Program consumption: 188500 units remaining",
+            "Program log: cu:<match_orders",
+            "Program consumption: 188000 units remaining",
+            "Program log: cu:<new_perp_order",
+            "Program log: cu:>settle",
+            "Program consumption: 187000 units remaining",
+            "Program consumption: 186000 units remaining",
+            "Program Drv111 failed: custom program error: 0xd2",
+        ];
+
+        let tree = CuTree::parse(logs);
+        assert_eq!(tree.roots.len(), 2);
+
+        let order = &tree.roots[0];
+        assert_eq!(order.label, "new_perp_order");
+        assert_eq!(order.consumed, 11_000);
+        assert_eq!(
+            order
+                .children
+                .iter()
+                .map(|child| child.consumed)
+                .collect::<Vec<_>>(),
+            vec![8_000, 500]
+        );
+        assert_eq!(order.self_consumed(), 2_500);
+
+        // Transaction failed inside the scope
+        assert_eq!(tree.roots[1].consumed, 1_000);
+
+        assert_eq!(tree.totals().get("match_orders"), Some(&(2, 8_500)));
+    }
+}
@@ -0,0 +1,172 @@
+use std::fmt::Display;
+
+use solana_pubkey::Pubkey;
+
+use crate::state::types::{account_type::AccountType, OrderSide};
+
+/// Errors produced by `drv-models` helpers.
+///
+/// `drv-errors` depends on this crate, so models can not return
+/// `DeriverseErrorKind` directly. Every variant mirrors the
+/// `DeriverseErrorKind` variant with the same name and fields, and
+/// `drv-errors` converts one into the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelsError {
+    InvalidDataLength {
+        expected: usize,
+        actual: usize,
+    },
+    InvalidQuantity {
+        value: i64,
+        min_value: i64,
+        max_value: i64,
+    },
+    InvalidPrice {
+        price: i64,
+        min_price: i64,
+        max_price: i64,
+    },
+    InvalidLeverage {
+        value: u32,
+        max: u32,
+    },
+    InvalidAccountTag {
+        expected_account_type: AccountType,
+        actual_tag: u32,
+        address: Pubkey,
+    },
+    InvalidVersion {
+        address: Pubkey,
+        account_type: AccountType,
+        expected: u32,
+        actual: u32,
+    },
+    InvalidDataAlignment {
+        address: Pubkey,
+    },
+    OffsetOutOfBounds {
+        address: Pubkey,
+        offset: usize,
+        len: usize,
+    },
+    InvalidAccountSize {
+        address: Pubkey,
+        account_type: AccountType,
+        expected: usize,
+        actual: usize,
+    },
+    /// Raw value does not decode into `OrderType`
+    UnknownOrderType {
+        order_type_raw: u8,
+    },
+    InvalidClientDataFormat,
+    OrderWasNotFound {
+        link: u32,
+        side: OrderSide,
+    },
+    InvalidBidOrdersCount,
+    InvalidAskOrdersCount,
+    InvalidBidLinesCount,
+    InvalidAskLinesCount,
+    ArithmeticOverflow,
+    InvalidQuoteOrdersAmount {
+        mask_amount: u32,
+        orders_amount: u32,
+    },
+    DivisionByZero,
+}
+
+impl Display for ModelsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidDataLength { expected, actual } => write!(
+                f,
+                "Invalid instructions data length, expected {} < actual {}",
+                expected, actual
+            ),
+            Self::InvalidQuantity {
+                value,
+                min_value,
+                max_value,
+            } => write!(
+                f,
+                "Invalid quantity value {}, acceptable range: {}..{}",
+                value, min_value, max_value
+            ),
+            Self::InvalidPrice {
+                price,
+                min_price,
+                max_price,
+            } => write!(
+                f,
+                "Invalid price value {}, acceptable range: {}..{}",
+                price, min_price, max_price
+            ),
+            Self::InvalidLeverage { value, max } => write!(
+                f,
+                "Invalid leverage, leverage {} > max leverage {} or equal to 0",
+                value, max
+            ),
+            Self::InvalidAccountTag {
+                expected_account_type,
+                actual_tag,
+                ..
+            } => write!(
+                f,
+                "Invalid account tag in account, expected {} != actual {}",
+                *expected_account_type as u32, actual_tag
+            ),
+            Self::InvalidVersion {
+                account_type,
+                expected,
+                actual,
+                ..
+            } => write!(
+                f,
+                "Invalid version in {:?}, expected {} != actual {}",
+                account_type, expected, actual
+            ),
+            Self::InvalidDataAlignment { address } => {
+                write!(f, "Invalid data alignment in {}", address)
+            }
+            Self::OffsetOutOfBounds { offset, len, .. } => {
+                write!(f, "Offset {} is out of bound for data len {}", offset, len)
+            }
+            Self::InvalidAccountSize {
+                account_type,
+                expected,
+                actual,
+                ..
+            } => write!(
+                f,
+                "Invalid accounts size in {} account, actual {} < expected {}",
+                account_type, actual, expected
+            ),
+            Self::UnknownOrderType { order_type_raw } => {
+                write!(f, "Unknown order type {}", order_type_raw)
+            }
+            Self::InvalidClientDataFormat => write!(f, "Invalid data format"),
+            Self::OrderWasNotFound { link, side } => write!(
+                f,
+                "Order on the side {} with link {} was not found",
+                side, link
+            ),
+            Self::InvalidBidOrdersCount => write!(f, "Invalid Bid Orders count"),
+            Self::InvalidAskOrdersCount => write!(f, "Invalid Ask Orders count"),
+            Self::InvalidBidLinesCount => write!(f, "Invalid Bid Lines count"),
+            Self::InvalidAskLinesCount => write!(f, "Invalid Ask Lines count"),
+            Self::ArithmeticOverflow => write!(f, "Arithmetic overflow"),
+            Self::InvalidQuoteOrdersAmount {
+                mask_amount,
+                orders_amount,
+            } => write!(
+                f,
+                "Invalid quote orders amount mask amount: {}, orders amount: {}",
+                mask_amount, orders_amount
+            ),
+            Self::DivisionByZero => write!(f, "Division by zero"),
+        }
+    }
+}
+
+impl std::error::Error for ModelsError {}
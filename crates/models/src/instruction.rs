@@ -0,0 +1,267 @@
+//! # Instruction module
+//! - Ties a raw instruction data slice to its `instruction_data` payload
+//! - Dispatches by the leading tag byte, which equals
+//!   `DrvInstruction::INSTRUCTION_NUMBER` of the instruction marker
+
+use bytemuck::Pod;
+use std::mem::size_of;
+
+use crate::{errors::ModelsError, instruction_constants::*, instruction_data::*};
+
+/// Reads `T` from the beginning of `data`.
+///
+/// Instruction data is not guaranteed to be aligned, so the payload is copied
+/// out. Trailing bytes (etc. quote orders after `SpotQuotesReplaceData`) are
+/// left to the caller.
+pub fn read_payload<T: Pod>(data: &[u8]) -> Result<T, ModelsError> {
+    let size = size_of::<T>();
+
+    data.get(..size)
+        .map(bytemuck::pod_read_unaligned)
+        .ok_or(ModelsError::InvalidDataLength {
+            expected: size,
+            actual: data.len(),
+        })
+}
+
+macro_rules! deriverse_instructions {
+    (
+        payload { $($variant:ident($marker:ty, $data:ty)),* $(,)? }
+        plain { $($plain_variant:ident($plain_marker:ty)),* $(,)? }
+    ) => {
+        $(
+            const _: () = assert!(
+                <$data>::TAG == <$marker as DrvInstruction>::INSTRUCTION_NUMBER,
+                concat!(stringify!($data), " is bound to another instruction than ", stringify!($marker)),
+            );
+        )*
+
+        /// Deriverse Instruction
+        ///
+        /// One variant per instruction supported by the program. Variants with
+        /// a payload carry the corresponding `instruction_data` struct,
+        /// instructions without one are unit variants.
+        #[derive(Clone, Copy)]
+        pub enum DeriverseInstruction {
+            $($variant($data),)*
+            $($plain_variant,)*
+        }
+
+        impl DeriverseInstruction {
+            /// Decodes instruction data by its tag byte
+            ///
+            /// ### Errors
+            /// - `InvalidDataLength` - data is empty or shorter than the payload
+            /// - `InvalidClientDataFormat` - unknown tag
+            pub fn decode(data: &[u8]) -> Result<Self, ModelsError> {
+                let tag = *data.first().ok_or(ModelsError::InvalidDataLength {
+                    expected: 1,
+                    actual: 0,
+                })?;
+
+                Ok(match tag {
+                    $(<$marker as DrvInstruction>::INSTRUCTION_NUMBER => {
+                        Self::$variant(read_payload::<$data>(data)?)
+                    })*
+                    $(<$plain_marker as DrvInstruction>::INSTRUCTION_NUMBER => {
+                        Self::$plain_variant
+                    })*
+                    _ => return Err(ModelsError::InvalidClientDataFormat),
+                })
+            }
+
+            /// Tag byte of the instruction, equals `INSTRUCTION_NUMBER`
+            pub const fn tag(&self) -> u8 {
+                match self {
+                    $(Self::$variant(_) => <$marker as DrvInstruction>::INSTRUCTION_NUMBER,)*
+                    $(Self::$plain_variant => {
+                        <$plain_marker as DrvInstruction>::INSTRUCTION_NUMBER
+                    })*
+                }
+            }
+
+            /// Minimal amount of accounts required by the instruction
+            pub const fn min_accounts(&self) -> usize {
+                match self {
+                    $(Self::$variant(_) => <$marker as DrvInstruction>::MIN_ACCOUNTS,)*
+                    $(Self::$plain_variant => <$plain_marker as DrvInstruction>::MIN_ACCOUNTS,)*
+                }
+            }
+        }
+    };
+}
+
+deriverse_instructions! {
+    payload {
+        NewOperator(NewOperatorInstruction, NewOperatorData),
+        NewRootAccount(NewRootAccountInstruction, NewRootAccountData),
+        PerpWithdraw(PerpWithdrawInstruction, PerpWithdrawData),
+        NewBaseCrncy(NewBaseCrncyInstruction, NewBaseCrncyData),
+        FeesDeposit(FeesDepositInstruction, FeesDepositData),
+        Deposit(DepositInstruction, DepositData),
+        Withdraw(WithdrawInstruction, WithdrawData),
+        NewInstrument(NewInstrumentInstruction, NewInstrumentData),
+        UpgradeToPerp(UpgradeToPerpInstruction, UpgradeToPerpData),
+        PerpDeposit(PerpDepositInstruction, PerpDepositData),
+        NewSpotOrder(NewSpotOrderInstruction, NewSpotOrderData),
+        SpotOrderCancel(SpotOrderCancelInstruction, SpotOrderCancelData),
+        SpotLp(SpotLpInstruction, SpotLpData),
+        SpotMassCancel(SpotMassCancelInstruction, SpotMassCancelData),
+        NewPerpOrder(NewPerpOrderInstruction, NewPerpOrderData),
+        Swap(SwapInstruction, SwapData),
+        Airdrop(AirdropInstruction, AirdropData),
+        PerpOrderCancel(PerpOrderCancelInstruction, PerpOrderCancelData),
+        Voting(VotingInstruction, VotingData),
+        SpotQuotesReplace(SpotQuotesReplaceInstruction, SpotQuotesReplaceData),
+        PerpMassCancel(PerpMassCancelInstruction, PerpMassCancelData),
+        PerpChangeLeverage(PerpChangeLeverageInstruction, PerpChangeLeverageData),
+        FeesWithdraw(FeesWithdrawInstruction, FeesWithdrawData),
+        SetInstrReadyForPerpUpgrade(
+            SetInstrReadyForPerpUpgradeInstruction,
+            SetInstrReadyForPerpUpgradeData
+        ),
+        PerpQuotesReplace(PerpQuotesReplaceInstruction, PerpQuotesReplaceData),
+        MoveSpotAvailFunds(MoveSpotAvailFundsInstruction, MoveSpotAvailFundsData),
+        ChangeRefProgram(ChangeRefProgramInstruction, ChangeRefProgramData),
+        PerpStatisticsReset(PerpStatisticsResetInstruction, PerpStatisticsResetData),
+        BuyMarketSeat(BuyMarketSeatInstruction, BuyMarketSeatData),
+        SellMarketSeat(SellMarketSeatInstruction, SellMarketSeatData),
+        NewPrivateClient(NewPrivateClientInstruction, NewPrivateClient),
+        ChangePointsProgramExpiration(
+            ChangePointsProgramExpirationInstruction,
+            PointsProgramExpiration
+        ),
+        SetVariance(SetVariance, SetVarianceData),
+        ChangeDenominator(ChangeDenominator, ChangeDenominatorData),
+        PerpClientsProcessing(PerpClientsProcessingInstruction, PerpClientsProcessingData),
+        SetSeatPurchasingFee(SetSeatPurchasingFee, SetSeatPurchasingFeeData),
+        ChangeVoting(ChangeVotingInstruction, ChangeVotingData),
+        GarbageCollector(GarbageCollectorInstruction, GarbageCollectorData),
+        ActivateClientRefProgram(ActivateClientRefProgram, ActivateClientRefProgramData),
+        CleanCandles(CleanCandlesInstruction, CleanCandlesData),
+        VmInitWithdraw(VmInitWithdraw, VmInitWithdrawData),
+        VmChangeWhitelist(VmChangeWhitelist, VmChangeWhitelistData),
+        WithdrawSwapFees(WithdrawSwapFeesInstruction, WithdrawSwapFeesData),
+        SetSAMMinQty(SetSAMMinQtyInstruction, SetSAMMinQtyData),
+        ChangeSAMFeesPolicy(ChangeSAMFeesPolictyInstruction, ChangeSAMFeesPolicyData),
+        SuspendInstrument(SuspendInstrumentInstruction, SuspendInstrumentData),
+    }
+    plain {
+        NewHolder(NewHolderInstruction),
+        NextVoting(NextVotingInstruction),
+        DividendsAllocation(DividendsAllocationInstruction),
+        DividendsClaim(DividendsClaimInstruction),
+        NewRefLink(NewRefLinkInstruction),
+        TerminatePrivateMode(TerminatePrivateModeInstruction),
+        ChangeAirdropAuthority(ChangeAirdropAuthority),
+        ChangePrivateModeAuthority(ChangePrivateModeAuthority),
+        VotingReset(VotingReset),
+        VmInitActivate(VmInitActivate),
+        VmInitActivateCancel(VmInitActivateCancel),
+        VmFinalizeActivate(VmFinalizeActivate),
+        VmInitDeactivate(VmInitDeactivate),
+        VmInitDeactivateCancel(VmInitDeactivateCancel),
+        VmFinalizeDeactivate(VmFinalizeDeactivate),
+        VmInitWithdrawCancel(VmInitWithdrawCancel),
+        VmInitWithdrawFinalize(VmInitWithdrawFinalize),
+        ExtendCandles(ExtendCandles),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::new_types::instrument::InstrId;
+
+    #[test]
+    fn test_decode_new_spot_order() {
+        let data = NewSpotOrderData {
+            tag: NewSpotOrderInstruction::INSTRUCTION_NUMBER,
+            ioc: 1,
+            order_type: 0,
+            side: 1,
+            instr_id: InstrId(3),
+            price: 1_000_000,
+            amount: 20_000,
+            edge_price: 0,
+        };
+
+        // Shift by one byte to make sure unaligned input is accepted
+        let mut buffer = vec![0u8];
+        buffer.extend_from_slice(bytemuck::bytes_of(&data));
+
+        match DeriverseInstruction::decode(&buffer[1..]) {
+            Ok(DeriverseInstruction::NewSpotOrder(decoded)) => {
+                assert_eq!(decoded.instr_id, InstrId(3));
+                assert_eq!(decoded.price, 1_000_000);
+                assert_eq!(decoded.amount, 20_000);
+            }
+            _ => panic!("Expected NewSpotOrder"),
+        }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(
+            DeriverseInstruction::decode(&[]).err(),
+            Some(ModelsError::InvalidDataLength {
+                expected: 1,
+                actual: 0
+            })
+        );
+
+        assert_eq!(
+            DeriverseInstruction::decode(&[NewSpotOrderInstruction::INSTRUCTION_NUMBER, 0, 0])
+                .err(),
+            Some(ModelsError::InvalidDataLength {
+                expected: size_of::<NewSpotOrderData>(),
+                actual: 3
+            })
+        );
+
+        assert_eq!(
+            DeriverseInstruction::decode(&[6]).err(),
+            Some(ModelsError::InvalidClientDataFormat)
+        );
+    }
+
+    #[test]
+    fn test_plain_instruction_tag() {
+        let instruction =
+            DeriverseInstruction::decode(&[NextVotingInstruction::INSTRUCTION_NUMBER]);
+
+        assert!(matches!(instruction, Ok(DeriverseInstruction::NextVoting)));
+        assert_eq!(
+            instruction.map(|instruction| instruction.tag()),
+            Ok(NextVotingInstruction::INSTRUCTION_NUMBER)
+        );
+    }
+
+    #[test]
+    fn test_instruction_data_parse() {
+        let data = NewSpotOrderData::new(1, 0, 1, InstrId(3), 1_000_000, 20_000, 0);
+        assert_eq!(data.tag, NewSpotOrderInstruction::INSTRUCTION_NUMBER);
+
+        let mut bytes = bytemuck::bytes_of(&data).to_vec();
+        assert!(matches!(
+            NewSpotOrderData::parse(&bytes),
+            Ok(NewSpotOrderData {
+                price: 1_000_000,
+                ..
+            })
+        ));
+        assert_eq!(
+            NewSpotOrderData::parse(&bytes[..3]).err(),
+            Some(ModelsError::InvalidDataLength {
+                expected: size_of::<NewSpotOrderData>(),
+                actual: 3
+            })
+        );
+
+        bytes[0] = NewPerpOrderInstruction::INSTRUCTION_NUMBER;
+        assert_eq!(
+            NewSpotOrderData::parse(&bytes).err(),
+            Some(ModelsError::InvalidClientDataFormat)
+        );
+    }
+}
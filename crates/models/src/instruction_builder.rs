@@ -0,0 +1,545 @@
+//! # Instruction builder module
+//! - Builders fill `instruction_data` payloads with the correct tag
+//! - Accounts are passed as `[AccountMeta; MIN_ACCOUNTS]`, so a wrong amount
+//!   of accounts is a compile time error
+//! - Accounts must follow the order expected by the program processor,
+//!   optional accounts are passed with `build_with_remaining`
+
+use bytemuck::Zeroable;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use crate::{
+    constants::MAX_QUOTE_ORDERS,
+    errors::ModelsError,
+    instruction_constants::*,
+    instruction_data::*,
+    new_types::{instrument::InstrId, version::Version},
+    state::types::{
+        instr_mask::InstrInputMask,
+        quote_status::{QuoteMask, QuoteOrder},
+        vm_status::VmMask,
+        OrderSide, OrderType, SAMFeeType,
+    },
+};
+
+fn instruction(
+    program_id: Pubkey,
+    data: Vec<u8>,
+    accounts: &[AccountMeta],
+    remaining: &[AccountMeta],
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: accounts.iter().chain(remaining).cloned().collect(),
+        data,
+    }
+}
+
+macro_rules! instruction_builders {
+    ($(
+        $builder:ident($marker:ty, $data:ty) {
+            new($($arg:ident: $arg_ty:ty),* $(,)?)
+            $(, $field:ident: $field_ty:ty)*
+            $(; cast $cast_field:ident: $cast_ty:ty)*
+        }
+    )*) => {$(
+        #[doc = concat!("Builder for `", stringify!($data), "` payload")]
+        #[derive(Clone, Copy)]
+        pub struct $builder {
+            data: $data,
+        }
+
+        impl $builder {
+            pub fn new($($arg: $arg_ty),*) -> Self {
+                let mut data = <$data>::zeroed();
+                data.tag = <$marker as DrvInstruction>::INSTRUCTION_NUMBER;
+                $(data.$arg = $arg;)*
+
+                Self { data }
+            }
+
+            $(
+                pub const fn $field(mut self, $field: $field_ty) -> Self {
+                    self.data.$field = $field;
+                    self
+                }
+            )*
+
+            $(
+                pub const fn $cast_field(mut self, $cast_field: $cast_ty) -> Self {
+                    self.data.$cast_field = $cast_field as u8;
+                    self
+                }
+            )*
+
+            pub const fn data(&self) -> &$data {
+                &self.data
+            }
+
+            pub fn to_bytes(&self) -> Vec<u8> {
+                bytemuck::bytes_of(&self.data).to_vec()
+            }
+
+            pub fn build(
+                &self,
+                program_id: Pubkey,
+                accounts: [AccountMeta; <$marker as DrvInstruction>::MIN_ACCOUNTS],
+            ) -> Instruction {
+                instruction(program_id, self.to_bytes(), &accounts, &[])
+            }
+
+            pub fn build_with_remaining(
+                &self,
+                program_id: Pubkey,
+                accounts: [AccountMeta; <$marker as DrvInstruction>::MIN_ACCOUNTS],
+                remaining: &[AccountMeta],
+            ) -> Instruction {
+                instruction(program_id, self.to_bytes(), &accounts, remaining)
+            }
+        }
+    )*};
+}
+
+macro_rules! plain_instruction_builders {
+    ($($builder:ident($marker:ty)),* $(,)?) => {$(
+        /// Builder for an instruction without payload, data is a single tag
+        /// byte
+        #[derive(Clone, Copy, Default)]
+        pub struct $builder;
+
+        impl $builder {
+            pub const fn new() -> Self {
+                Self
+            }
+
+            pub fn to_bytes(&self) -> Vec<u8> {
+                vec![<$marker as DrvInstruction>::INSTRUCTION_NUMBER]
+            }
+
+            pub fn build(
+                &self,
+                program_id: Pubkey,
+                accounts: [AccountMeta; <$marker as DrvInstruction>::MIN_ACCOUNTS],
+            ) -> Instruction {
+                instruction(program_id, self.to_bytes(), &accounts, &[])
+            }
+
+            pub fn build_with_remaining(
+                &self,
+                program_id: Pubkey,
+                accounts: [AccountMeta; <$marker as DrvInstruction>::MIN_ACCOUNTS],
+                remaining: &[AccountMeta],
+            ) -> Instruction {
+                instruction(program_id, self.to_bytes(), &accounts, remaining)
+            }
+        }
+    )*};
+}
+
+instruction_builders! {
+    NewOperatorBuilder(NewOperatorInstruction, NewOperatorData) {
+        new(version: Version)
+    }
+    NewRootAccountBuilder(NewRootAccountInstruction, NewRootAccountData) {
+        new(version: Version, lut_slot: u32);
+        cast private_mode: bool
+    }
+    PerpWithdrawBuilder(PerpWithdrawInstruction, PerpWithdrawData) {
+        new(instr_id: InstrId),
+        amount: i64
+    }
+    NewBaseCrncyBuilder(NewBaseCrncyInstruction, NewBaseCrncyData) {
+        new(denominator: f64)
+    }
+    FeesDepositBuilder(FeesDepositInstruction, FeesDepositData) {
+        new(token_id: u32),
+        amount: i64
+    }
+    DepositBuilder(DepositInstruction, DepositData) {
+        new(token_id: u32),
+        amount: i64,
+        lut_slot: u32,
+        ref_id: u32,
+        custom_id: i64;
+        cast deposit_all: bool
+    }
+    WithdrawBuilder(WithdrawInstruction, WithdrawData) {
+        new(token_id: u32),
+        amount: i64,
+        custom_id: i64
+    }
+    NewInstrumentBuilder(NewInstrumentInstruction, NewInstrumentData) {
+        new(crncy_token_id: u32, price: i64),
+        mask: InstrInputMask,
+        lut_slot: u32,
+        min_qty: i64,
+        fixed_fee_rate: f64
+    }
+    UpgradeToPerpBuilder(UpgradeToPerpInstruction, UpgradeToPerpData) {
+        new(instr_id: InstrId)
+    }
+    PerpDepositBuilder(PerpDepositInstruction, PerpDepositData) {
+        new(instr_id: InstrId),
+        amount: i64
+    }
+    NewSpotOrderBuilder(NewSpotOrderInstruction, NewSpotOrderData) {
+        new(instr_id: InstrId),
+        price: i64,
+        amount: i64,
+        edge_price: i64;
+        cast ioc: bool;
+        cast order_type: OrderType;
+        cast side: OrderSide
+    }
+    SpotOrderCancelBuilder(SpotOrderCancelInstruction, SpotOrderCancelData) {
+        new(instr_id: InstrId, order_id: i64);
+        cast side: OrderSide
+    }
+    SpotLpBuilder(SpotLpInstruction, SpotLpData) {
+        new(instr_id: InstrId),
+        amount: i64,
+        min_price: i64,
+        max_price: i64;
+        cast side: OrderSide
+    }
+    SpotMassCancelBuilder(SpotMassCancelInstruction, SpotMassCancelData) {
+        new(instr_id: InstrId)
+    }
+    NewPerpOrderBuilder(NewPerpOrderInstruction, NewPerpOrderData) {
+        new(instr_id: InstrId),
+        leverage: u8,
+        price: i64,
+        amount: i64,
+        edge_price: i64;
+        cast ioc: bool;
+        cast order_type: OrderType;
+        cast side: OrderSide
+    }
+    SwapBuilder(SwapInstruction, SwapData) {
+        new(instr_id: InstrId),
+        price: i64,
+        amount: i64,
+        min_amount_out: i64;
+        cast input_crncy: bool
+    }
+    AirdropBuilder(AirdropInstruction, AirdropData) {
+        new(ratio: f64)
+    }
+    PerpOrderCancelBuilder(PerpOrderCancelInstruction, PerpOrderCancelData) {
+        new(instr_id: InstrId, order_id: i64);
+        cast side: OrderSide
+    }
+    VotingBuilder(VotingInstruction, VotingData) {
+        new(voting_counter: u32, choice: u8)
+    }
+    PerpMassCancelBuilder(PerpMassCancelInstruction, PerpMassCancelData) {
+        new(instr_id: InstrId)
+    }
+    PerpChangeLeverageBuilder(PerpChangeLeverageInstruction, PerpChangeLeverageData) {
+        new(instr_id: InstrId, leverage: u8)
+    }
+    FeesWithdrawBuilder(FeesWithdrawInstruction, FeesWithdrawData) {
+        new(token_id: u32),
+        amount: i64
+    }
+    SetInstrReadyForPerpUpgradeBuilder(
+        SetInstrReadyForPerpUpgradeInstruction,
+        SetInstrReadyForPerpUpgradeData
+    ) {
+        new(instr_id: InstrId)
+    }
+    MoveSpotAvailFundsBuilder(MoveSpotAvailFundsInstruction, MoveSpotAvailFundsData) {
+        new(instr_id: InstrId)
+    }
+    ChangeRefProgramBuilder(ChangeRefProgramInstruction, ChangeRefProgramData) {
+        new(
+            ref_program_duration: u32,
+            ref_link_duration: u32,
+            ref_discount: f64,
+            ref_ratio: f64,
+        )
+    }
+    PerpStatisticsResetBuilder(PerpStatisticsResetInstruction, PerpStatisticsResetData) {
+        new(instr_id: InstrId)
+    }
+    BuyMarketSeatBuilder(BuyMarketSeatInstruction, BuyMarketSeatData) {
+        new(instr_id: InstrId),
+        edge_price: i64,
+        amount: i64
+    }
+    SellMarketSeatBuilder(SellMarketSeatInstruction, SellMarketSeatData) {
+        new(instr_id: InstrId),
+        edge_price: i64
+    }
+    NewPrivateClientBuilder(NewPrivateClientInstruction, NewPrivateClient) {
+        new(expiration_time: u32)
+    }
+    ChangePointsProgramExpirationBuilder(
+        ChangePointsProgramExpirationInstruction,
+        PointsProgramExpiration
+    ) {
+        new(new_expiration_time: u32)
+    }
+    SetVarianceBuilder(SetVariance, SetVarianceData) {
+        new(instr_id: InstrId, variance: f64)
+    }
+    ChangeDenominatorBuilder(ChangeDenominator, ChangeDenominatorData) {
+        new(base_crncy_id: u32, denominator: f64)
+    }
+    PerpClientsProcessingBuilder(PerpClientsProcessingInstruction, PerpClientsProcessingData) {
+        new(instr_id: InstrId)
+    }
+    SetSeatPurchasingFeeBuilder(SetSeatPurchasingFee, SetSeatPurchasingFeeData) {
+        new(fee: f64)
+    }
+    ChangeVotingBuilder(ChangeVotingInstruction, ChangeVotingData) {
+        new(voting_counter: u32, new_choice: u8)
+    }
+    GarbageCollectorBuilder(GarbageCollectorInstruction, GarbageCollectorData) {
+        new(instr_id: InstrId)
+    }
+    ActivateClientRefProgramBuilder(ActivateClientRefProgram, ActivateClientRefProgramData) {
+        new(ref_id: u32)
+    }
+    CleanCandlesBuilder(CleanCandlesInstruction, CleanCandlesData) {
+        new(instr_id: InstrId)
+    }
+    VmInitWithdrawBuilder(VmInitWithdraw, VmInitWithdrawData) {
+        new(token_id: u32),
+        amount: i64
+    }
+    VmChangeWhitelistBuilder(VmChangeWhitelist, VmChangeWhitelistData) {
+        new(mask: VmMask, whitelist: [u32; 8])
+    }
+    WithdrawSwapFeesBuilder(WithdrawSwapFeesInstruction, WithdrawSwapFeesData) {
+        new(instr_id: InstrId),
+        amount: i64
+    }
+    SetSAMMinQtyBuilder(SetSAMMinQtyInstruction, SetSAMMinQtyData) {
+        new(instr_id: InstrId, min_qty: i64)
+    }
+    ChangeSAMFeesPolicyBuilder(ChangeSAMFeesPolictyInstruction, ChangeSAMFeesPolicyData) {
+        new(instr_id: InstrId),
+        fee_rate: f64;
+        cast sam_fee_type: SAMFeeType
+    }
+    SuspendInstrumentBuilder(SuspendInstrumentInstruction, SuspendInstrumentData) {
+        new(instr_id: InstrId)
+    }
+}
+
+plain_instruction_builders! {
+    NewHolderBuilder(NewHolderInstruction),
+    NextVotingBuilder(NextVotingInstruction),
+    DividendsAllocationBuilder(DividendsAllocationInstruction),
+    DividendsClaimBuilder(DividendsClaimInstruction),
+    NewRefLinkBuilder(NewRefLinkInstruction),
+    TerminatePrivateModeBuilder(TerminatePrivateModeInstruction),
+    ChangeAirdropAuthorityBuilder(ChangeAirdropAuthority),
+    ChangePrivateModeAuthorityBuilder(ChangePrivateModeAuthority),
+    VotingResetBuilder(VotingReset),
+    VmInitActivateBuilder(VmInitActivate),
+    VmInitActivateCancelBuilder(VmInitActivateCancel),
+    VmFinalizeActivateBuilder(VmFinalizeActivate),
+    VmInitDeactivateBuilder(VmInitDeactivate),
+    VmInitDeactivateCancelBuilder(VmInitDeactivateCancel),
+    VmFinalizeDeactivateBuilder(VmFinalizeDeactivate),
+    VmInitWithdrawCancelBuilder(VmInitWithdrawCancel),
+    VmInitWithdrawFinalizeBuilder(VmInitWithdrawFinalize),
+    ExtendCandlesBuilder(ExtendCandles),
+}
+
+macro_rules! quotes_replace_builders {
+    ($($builder:ident($marker:ty, $data:ty)),* $(,)?) => {$(
+        /// Quotes replace builder
+        ///
+        /// Payload header is followed by `mask.amount()` `QuoteOrder` records,
+        /// side of each record is stored in the `mask`
+        #[derive(Clone, Copy)]
+        pub struct $builder {
+            data: $data,
+            quotes: [QuoteOrder; MAX_QUOTE_ORDERS as usize],
+        }
+
+        impl $builder {
+            pub fn new(instr_id: InstrId) -> Self {
+                let mut data = <$data>::zeroed();
+                data.tag = <$marker as DrvInstruction>::INSTRUCTION_NUMBER;
+                data.instr_id = instr_id;
+                data.mask = QuoteMask::new(0);
+
+                Self {
+                    data,
+                    quotes: [QuoteOrder::zeroed(); MAX_QUOTE_ORDERS as usize],
+                }
+            }
+
+            pub const fn order_type(mut self, order_type: OrderType) -> Self {
+                self.data.order_type = order_type as u8;
+                self
+            }
+
+            /// Appends a quote
+            ///
+            /// ### Errors
+            /// - `InvalidQuoteOrdersAmount` - `MAX_QUOTE_ORDERS` quotes are
+            ///   already added
+            pub fn quote(mut self, side: OrderSide, quote: QuoteOrder) -> Result<Self, ModelsError> {
+                let position = self.data.mask.amount() as usize;
+                if position >= MAX_QUOTE_ORDERS as usize {
+                    return Err(ModelsError::InvalidQuoteOrdersAmount {
+                        mask_amount: MAX_QUOTE_ORDERS as u32,
+                        orders_amount: position as u32 + 1,
+                    });
+                }
+
+                self.data.mask.set_quote(position, side);
+                self.data.mask.set_amount(position as u8 + 1);
+                self.quotes[position] = quote;
+                Ok(self)
+            }
+
+            pub const fn data(&self) -> &$data {
+                &self.data
+            }
+
+            pub fn to_bytes(&self) -> Vec<u8> {
+                let amount = self.data.mask.amount() as usize;
+
+                let mut bytes = bytemuck::bytes_of(&self.data).to_vec();
+                bytes.extend_from_slice(bytemuck::cast_slice(&self.quotes[..amount]));
+                bytes
+            }
+
+            pub fn build(
+                &self,
+                program_id: Pubkey,
+                accounts: [AccountMeta; <$marker as DrvInstruction>::MIN_ACCOUNTS],
+            ) -> Instruction {
+                instruction(program_id, self.to_bytes(), &accounts, &[])
+            }
+
+            pub fn build_with_remaining(
+                &self,
+                program_id: Pubkey,
+                accounts: [AccountMeta; <$marker as DrvInstruction>::MIN_ACCOUNTS],
+                remaining: &[AccountMeta],
+            ) -> Instruction {
+                instruction(program_id, self.to_bytes(), &accounts, remaining)
+            }
+        }
+    )*};
+}
+
+quotes_replace_builders! {
+    SpotQuotesReplaceBuilder(SpotQuotesReplaceInstruction, SpotQuotesReplaceData),
+    PerpQuotesReplaceBuilder(PerpQuotesReplaceInstruction, PerpQuotesReplaceData),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::DeriverseInstruction;
+
+    #[test]
+    fn test_new_perp_order_builder() {
+        let program_id = Pubkey::new_unique();
+        let accounts = std::array::from_fn(|_| AccountMeta::new(Pubkey::new_unique(), false));
+
+        let instruction = NewPerpOrderBuilder::new(InstrId(5))
+            .side(OrderSide::Ask)
+            .order_type(OrderType::Market)
+            .ioc(true)
+            .leverage(10)
+            .price(2_000_000_000)
+            .amount(50_000)
+            .build(program_id, accounts);
+
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(
+            instruction.accounts.len(),
+            NewPerpOrderInstruction::MIN_ACCOUNTS
+        );
+
+        match DeriverseInstruction::decode(&instruction.data) {
+            Ok(DeriverseInstruction::NewPerpOrder(data)) => {
+                assert_eq!(data.instr_id, InstrId(5));
+                assert_eq!(data.side, OrderSide::Ask as u8);
+                assert_eq!(data.order_type, OrderType::Market as u8);
+                assert_eq!(data.ioc, 1);
+                assert_eq!(data.leverage, 10);
+                assert_eq!(data.price, 2_000_000_000);
+                assert_eq!(data.amount, 50_000);
+            }
+            _ => panic!("Expected NewPerpOrder"),
+        }
+    }
+
+    #[test]
+    fn test_quotes_replace_builder() {
+        let quote = QuoteOrder {
+            new_price: 1_000_000,
+            new_qty: 10_000,
+            old_id: 0,
+        };
+
+        let builder = SpotQuotesReplaceBuilder::new(InstrId(1))
+            .quote(OrderSide::Bid, quote)
+            .and_then(|builder| builder.quote(OrderSide::Ask, quote))
+            .expect("Two quotes fit");
+
+        let bytes = builder.to_bytes();
+        assert_eq!(
+            bytes.len(),
+            size_of::<SpotQuotesReplaceData>() + 2 * size_of::<QuoteOrder>()
+        );
+        assert_eq!(builder.data().mask.amount(), 2);
+        assert_eq!(builder.data().mask.quote_side(1), OrderSide::Ask);
+    }
+
+    #[test]
+    fn test_build_with_remaining() {
+        let accounts = std::array::from_fn(|_| AccountMeta::new(Pubkey::new_unique(), false));
+        let remaining = [AccountMeta::new_readonly(Pubkey::new_unique(), false)];
+
+        let instruction = NextVotingBuilder::new().build_with_remaining(
+            Pubkey::new_unique(),
+            accounts,
+            &remaining,
+        );
+
+        assert_eq!(
+            instruction.data,
+            vec![NextVotingInstruction::INSTRUCTION_NUMBER]
+        );
+        assert_eq!(
+            instruction.accounts.len(),
+            NextVotingInstruction::MIN_ACCOUNTS + 1
+        );
+    }
+
+    #[test]
+    fn test_quotes_replace_builder_overflow() {
+        let quote = QuoteOrder {
+            new_price: 1_000_000,
+            new_qty: 10_000,
+            old_id: 0,
+        };
+
+        let builder = (0..MAX_QUOTE_ORDERS)
+            .try_fold(PerpQuotesReplaceBuilder::new(InstrId(1)), |builder, _| {
+                builder.quote(OrderSide::Bid, quote)
+            });
+
+        assert_eq!(
+            builder
+                .and_then(|builder| builder.quote(OrderSide::Ask, quote))
+                .err(),
+            Some(ModelsError::InvalidQuoteOrdersAmount {
+                mask_amount: MAX_QUOTE_ORDERS as u32,
+                orders_amount: MAX_QUOTE_ORDERS as u32 + 1,
+            })
+        );
+    }
+}
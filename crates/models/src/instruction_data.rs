@@ -1,13 +1,17 @@
 use crate::{
+    instruction_constants::*,
     new_types::{instrument::InstrId, version::Version},
     state::types::{
         instr_mask::InstrInputMask, quote_status::QuoteMask, vm_status::VmMask, SAMFeeType,
     },
 };
 use bytemuck::{Pod, Zeroable};
+use drv_macros::DrvInstructionData;
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(NewOperatorInstruction)]
 /// New Operator Data
 ///
 /// **Used in:** `new_operator` instruction
@@ -18,13 +22,17 @@ use bytemuck::{Pod, Zeroable};
 /// - `version` - smart contract version
 pub struct NewOperatorData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub version: Version,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(NewRootAccountInstruction)]
 /// New Root Account Data
 ///
 /// **Used in:** `new_root_account` instruction
@@ -38,13 +46,16 @@ pub struct NewOperatorData {
 pub struct NewRootAccountData {
     pub tag: u8,
     pub private_mode: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub version: Version,
     pub lut_slot: u32,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(NewSpotOrderInstruction)]
 /// New Spot Order Data
 ///
 /// **Used in:** `new_spot_order` instruction
@@ -62,7 +73,9 @@ pub struct NewRootAccountData {
 pub struct NewSpotOrderData {
     pub tag: u8,
     pub ioc: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_type"))]
     pub order_type: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
     pub instr_id: InstrId,
     pub price: i64,
@@ -71,10 +84,12 @@ pub struct NewSpotOrderData {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(NewPerpOrderInstruction)]
 /// New Perp Order Data
 ///
-/// **Used in:** `new_spot_order` instruction
+/// **Used in:** `new_perp_order` instruction
 ///
 /// **Tag:** `19`
 ///
@@ -91,10 +106,15 @@ pub struct NewPerpOrderData {
     pub tag: u8, //19
     pub ioc: u8,
     pub leverage: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_type"))]
     pub order_type: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
     pub instr_id: InstrId,
     pub price: i64,
@@ -103,7 +123,9 @@ pub struct NewPerpOrderData {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(PerpChangeLeverageInstruction)]
 /// New Change Leverage Data
 ///
 /// **Used in:** `perp_change_leverage` instruction
@@ -116,12 +138,15 @@ pub struct NewPerpOrderData {
 pub struct PerpChangeLeverageData {
     pub tag: u8,
     pub leverage: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(PerpStatisticsResetInstruction)]
 /// Perp Statistics Reset Data
 ///
 /// **Used in:** `perp_statistic_reset` instruction
@@ -132,13 +157,17 @@ pub struct PerpChangeLeverageData {
 /// - `instr_id` - Instr pair id
 pub struct PerpStatisticsResetData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(SpotOrderCancelInstruction)]
 /// Spot Order Cancel Data
 ///
 /// **Used in:** `spot_order_cancel` instruction
@@ -151,14 +180,18 @@ pub struct PerpStatisticsResetData {
 /// - `order_id` - Orders id in the system
 pub struct SpotOrderCancelData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
     pub order_id: i64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(SpotMassCancelInstruction)]
 /// Spot Mass Cancel Data
 ///
 /// **Used in:** `spot_mass_cancel` instruction
@@ -175,7 +208,9 @@ pub struct SpotMassCancelData {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(SpotLpInstruction)]
 /// Spot LP Data
 ///
 /// **Used in:** `spot_lp` instruction
@@ -190,7 +225,9 @@ pub struct SpotMassCancelData {
 /// - `max_price` - Price used max slippage bound calculations
 pub struct SpotLpData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
     pub amount: i64,
@@ -199,7 +236,9 @@ pub struct SpotLpData {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(NewInstrumentInstruction)]
 /// New Instrument Data
 ///
 /// **Used in:** `new_instrument` instruction
@@ -213,7 +252,9 @@ pub struct SpotLpData {
 pub struct NewInstrumentData {
     pub tag: u8,
     pub mask: InstrInputMask,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
     pub crncy_token_id: u32,
     pub lut_slot: u32,
@@ -223,7 +264,9 @@ pub struct NewInstrumentData {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(DepositInstruction)]
 /// Deposit Data
 ///
 /// **Used in:** `deposit` instruction
@@ -241,6 +284,7 @@ pub struct DepositData {
     pub tag: u8,
     pub competition_id: u8,
     pub deposit_all: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
     pub token_id: u32,
     pub amount: i64,
@@ -250,7 +294,9 @@ pub struct DepositData {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(FeesDepositInstruction)]
 /// Fees Deposit Data
 ///
 /// **Used in:** `fees_deposit` instruction
@@ -262,14 +308,18 @@ pub struct DepositData {
 /// - `amount` - Amount of tokens client wants to prepay
 pub struct FeesDepositData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub token_id: u32,
     pub amount: i64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(FeesWithdrawInstruction)]
 /// Fees Withdraw Data
 ///
 /// **Used in:** `fees_withdraw` instruction
@@ -281,14 +331,18 @@ pub struct FeesDepositData {
 /// - `amount` - Amount of tokens client wants to withdraw
 pub struct FeesWithdrawData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub token_id: u32,
     pub amount: i64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(PerpDepositInstruction)]
 /// Perp Deposit Data
 ///
 /// **Used in:** `perp_deposit` instruction
@@ -300,14 +354,18 @@ pub struct FeesWithdrawData {
 /// - `amount` - Amount of tokens client wants to move from spot to perp
 pub struct PerpDepositData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
     pub amount: i64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(MoveSpotAvailFundsInstruction)]
 /// Move Spot Available Funds Data
 ///
 /// **Used in:** `move_spot_avail_funds` instruction
@@ -318,13 +376,17 @@ pub struct PerpDepositData {
 /// - `instr_id` - Instr pair id
 pub struct MoveSpotAvailFundsData {
     pub tag: u8, //43
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(PerpWithdrawInstruction)]
 /// Perp Withdraw Data
 ///
 /// **Used in:** `perp_withdraw` instruction
@@ -336,14 +398,18 @@ pub struct MoveSpotAvailFundsData {
 /// - `amount` - Amount of tokens client wants to move from perp to spot
 pub struct PerpWithdrawData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
     pub amount: i64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(WithdrawInstruction)]
 /// Withdraw Data
 ///
 /// **Used in:** `withdraw` instruction
@@ -355,7 +421,9 @@ pub struct PerpWithdrawData {
 /// - `amount` - Amount of tokens to withdraw
 pub struct WithdrawData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8, // <- bump
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub token_id: u32,
     pub amount: i64,
@@ -363,7 +431,9 @@ pub struct WithdrawData {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(SwapInstruction)]
 /// New Swap Data
 ///
 /// **Used in:** `swap` instruction
@@ -379,6 +449,7 @@ pub struct WithdrawData {
 pub struct SwapData {
     pub tag: u8,
     pub input_crncy: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
     pub price: i64,
@@ -387,7 +458,9 @@ pub struct SwapData {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(SpotQuotesReplaceInstruction)]
 /// Spot Quotes Replace Data
 ///
 /// **Used in:** `spot_quotes_replace` instruction
@@ -400,16 +473,22 @@ pub struct SwapData {
 pub struct SpotQuotesReplaceData {
     pub tag: u8,
     pub bump: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_type"))]
     pub order_type: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
     pub mask: QuoteMask,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(PerpQuotesReplaceInstruction)]
 /// Perp Quotes Replace Data
 ///
 /// **Used in:** `perp_quotes_replace` instruction
@@ -422,16 +501,22 @@ pub struct SpotQuotesReplaceData {
 pub struct PerpQuotesReplaceData {
     pub tag: u8,
     pub bump: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_type"))]
     pub order_type: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
     pub mask: QuoteMask,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(VotingInstruction)]
 /// Voting Data
 ///
 /// **Used in:** `voting` instruction
@@ -444,12 +529,15 @@ pub struct PerpQuotesReplaceData {
 pub struct VotingData {
     pub tag: u8,
     pub choice: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub voting_counter: u32,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(AirdropInstruction)]
 /// Airdrop Data
 ///
 /// **Used in:** `airdrop` instruction
@@ -460,14 +548,19 @@ pub struct VotingData {
 /// - `ratio` - ratio DRVS token to airdrop token
 pub struct AirdropData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
     pub ratio: f64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(UpgradeToPerpInstruction)]
 /// Upgrade To Perp
 ///
 /// **Used in:** `upgrade_to_perp` instruction
@@ -478,13 +571,17 @@ pub struct AirdropData {
 /// - `instr_id` - Upgradable instrument pair id
 pub struct UpgradeToPerpData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(SetInstrReadyForPerpUpgradeInstruction)]
 /// Set Instrument Ready For Perp Upgrade Data
 ///
 /// **Used in:** `set_instr_ready_for_perp_upgrade` instruction
@@ -495,13 +592,17 @@ pub struct UpgradeToPerpData {
 /// - `instr_id` - Instrument pair id
 pub struct SetInstrReadyForPerpUpgradeData {
     pub tag: u8, // 41
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(PerpOrderCancelInstruction)]
 /// Perp Order Cancel Data
 ///
 /// **Used in:** `perp_order_cancel` instruction
@@ -514,14 +615,18 @@ pub struct SetInstrReadyForPerpUpgradeData {
 /// - `order_id` - Orders id in the system
 pub struct PerpOrderCancelData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
     pub order_id: i64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(PerpMassCancelInstruction)]
 /// Perp Mass Cancel Data
 ///
 /// **Used in:** `perp_mass_cancel` instruction
@@ -532,13 +637,17 @@ pub struct PerpOrderCancelData {
 /// - `instr_id` - Instr pair id
 pub struct PerpMassCancelData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(ChangeRefProgramInstruction)]
 /// Change Ref Program Data
 ///
 /// **Used in:** `change_ref_program` instruction
@@ -552,8 +661,11 @@ pub struct PerpMassCancelData {
 /// - `ref_ratio` - New rerral ratio
 pub struct ChangeRefProgramData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
     pub ref_program_duration: u32,
     pub ref_link_duration: u32,
@@ -562,7 +674,9 @@ pub struct ChangeRefProgramData {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(BuyMarketSeatInstruction)]
 /// Buy Market Seat Data
 ///
 /// **Used in:** `buy_market_seat` instruction
@@ -575,7 +689,9 @@ pub struct ChangeRefProgramData {
 /// - `edge_price` - Upper slippage bound for market seat purchase
 pub struct BuyMarketSeatData {
     pub tag: u8, //47
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
     pub edge_price: i64,
@@ -583,7 +699,9 @@ pub struct BuyMarketSeatData {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(SellMarketSeatInstruction)]
 /// Sell Market Seat Data
 ///
 /// **Used in:** `sell_market_seat` instruction
@@ -595,14 +713,18 @@ pub struct BuyMarketSeatData {
 /// - `edge_price` - Lower slippage bound for market seat purchase
 pub struct SellMarketSeatData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
     pub edge_price: i64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(NewPrivateClientInstruction)]
 /// New Private Client
 ///
 /// **Used in:** `new_private_client` instruction
@@ -613,13 +735,17 @@ pub struct SellMarketSeatData {
 /// - `expiration_time` - Clients position in queue expiration time
 pub struct NewPrivateClient {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub expiration_time: u32,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(ChangePointsProgramExpirationInstruction)]
 /// Points Program Expiration
 ///
 /// **Used in:** `change_points_program_expiration` instruction
@@ -630,13 +756,17 @@ pub struct NewPrivateClient {
 /// - `new_expiration_time` - New points program expiration time
 pub struct PointsProgramExpiration {
     pub tag: u8, //51
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub new_expiration_time: u32,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(SetVariance)]
 /// Set Variance Data
 ///
 /// **Tag** `54`
@@ -645,14 +775,18 @@ pub struct PointsProgramExpiration {
 /// - `variance` - Current price variance of given instrument
 pub struct SetVarianceData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
     pub variance: f64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(ChangeDenominator)]
 /// Change Denominator
 ///
 /// **Tag** `56`
@@ -662,14 +796,18 @@ pub struct SetVarianceData {
 /// - `denominator` - New denominator
 pub struct ChangeDenominatorData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub base_crncy_id: u32,
     pub denominator: f64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(NewBaseCrncyInstruction)]
 /// New Base Crncy
 ///
 /// **Tag** `4`
@@ -678,14 +816,19 @@ pub struct ChangeDenominatorData {
 /// - `denominator` - New denominator
 pub struct NewBaseCrncyData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
     pub denominator: f64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(PerpClientsProcessingInstruction)]
 /// Perp Clients Processing Data
 ///
 /// **Tag** `57`
@@ -694,13 +837,17 @@ pub struct NewBaseCrncyData {
 /// - `instr_id` - Instruments Id
 pub struct PerpClientsProcessingData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(SetSeatPurchasingFee)]
 /// Set Seat purchasing Fee
 ///
 /// **Tag** `58`
@@ -709,17 +856,22 @@ pub struct PerpClientsProcessingData {
 /// - `fee` - seat fee, aligned by admin
 pub struct SetSeatPurchasingFeeData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
     pub fee: f64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(ChangeVotingInstruction)]
 /// Change Vote Data
 ///
-/// **Used in:** `chante_vote` instruction
+/// **Used in:** `change_voting` instruction
 ///
 /// **Tag:** `59`
 ///
@@ -729,12 +881,15 @@ pub struct SetSeatPurchasingFeeData {
 pub struct ChangeVotingData {
     pub tag: u8,
     pub new_choice: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub voting_counter: u32,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(GarbageCollectorInstruction)]
 /// Garbage Collector Data
 ///
 /// **Tag:** `60`
@@ -743,13 +898,17 @@ pub struct ChangeVotingData {
 /// - `instr_id` - Instrument Id
 pub struct GarbageCollectorData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(ActivateClientRefProgram)]
 /// Set Ref Id Data
 ///
 /// **Tag** `61`
@@ -758,13 +917,17 @@ pub struct GarbageCollectorData {
 /// - `ref_id` - New referral id
 pub struct ActivateClientRefProgramData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub ref_id: u32,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(CleanCandlesInstruction)]
 /// Clean Candles Data
 ///
 /// **Tag** `62`
@@ -773,33 +936,45 @@ pub struct ActivateClientRefProgramData {
 /// - `instr_id` - instrument id
 pub struct CleanCandlesData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(VmInitWithdraw)]
 pub struct VmInitWithdrawData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub token_id: u32,
     pub amount: i64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(VmChangeWhitelist)]
 pub struct VmChangeWhitelistData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub mask: VmMask,
     pub whitelist: [u32; 8],
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(WithdrawSwapFeesInstruction)]
 /// Perp Withdraw Data
 ///
 /// **Tag:** `74`
@@ -809,37 +984,51 @@ pub struct VmChangeWhitelistData {
 /// - `amount` - Amount of tokens client wants to move from perp to spot
 pub struct WithdrawSwapFeesData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
     pub amount: i64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(SetSAMMinQtyInstruction)]
 pub struct SetSAMMinQtyData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
     pub min_qty: i64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(ChangeSAMFeesPolictyInstruction)]
 pub struct ChangeSAMFeesPolicyData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::sam_fee_type"))]
     pub sam_fee_type: u8, // 0 - zero fees, 1 - fixed_fees
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
     pub fee_rate: f64,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
+#[derive(Clone, Copy, Pod, Zeroable, DrvInstructionData)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_instruction(SuspendInstrumentInstruction)]
 pub struct SuspendInstrumentData {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub instr_id: InstrId,
 }
@@ -0,0 +1,319 @@
+//! # Instruction params module
+//! - Checked counterparts of raw `instruction_data` payloads
+//! - Raw flags are decoded into domain types and trading limits are applied,
+//!   so the program and off-chain clients share the same checks
+
+use crate::{
+    constants::{
+        perp::MAX_PERP_LEVERAGE,
+        trading_limitations::{MAX_PRICE, MIN_PRICE, MIN_QTY, SPOT_MAX_AMOUNT},
+    },
+    errors::ModelsError,
+    instruction_data::*,
+    new_types::instrument::InstrId,
+    state::types::{OrderSide, OrderType, SAMFeeType},
+};
+
+/// Checks `price` is within `MIN_PRICE..=MAX_PRICE`
+pub const fn check_price(price: i64) -> Result<i64, ModelsError> {
+    if price < MIN_PRICE || price > MAX_PRICE {
+        return Err(ModelsError::InvalidPrice {
+            price,
+            min_price: MIN_PRICE,
+            max_price: MAX_PRICE,
+        });
+    }
+
+    Ok(price)
+}
+
+/// Checks `qty` is within `MIN_QTY..=max_qty`
+pub const fn check_qty(qty: i64, max_qty: i64) -> Result<i64, ModelsError> {
+    if qty < MIN_QTY || qty > max_qty {
+        return Err(ModelsError::InvalidQuantity {
+            value: qty,
+            min_value: MIN_QTY,
+            max_value: max_qty,
+        });
+    }
+
+    Ok(qty)
+}
+
+/// Checks `leverage` is within `1..=MAX_PERP_LEVERAGE`
+pub const fn check_leverage(leverage: u8) -> Result<u8, ModelsError> {
+    if leverage == 0 || leverage > MAX_PERP_LEVERAGE {
+        return Err(ModelsError::InvalidLeverage {
+            value: leverage as u32,
+            max: MAX_PERP_LEVERAGE as u32,
+        });
+    }
+
+    Ok(leverage)
+}
+
+/// Checks order price, **Market** orders ignore it
+const fn check_order_price(order_type: OrderType, price: i64) -> Result<i64, ModelsError> {
+    match order_type {
+        OrderType::Market => Ok(price),
+        _ => check_price(price),
+    }
+}
+
+/// New Spot Order Params
+///
+/// Checked `NewSpotOrderData`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NewSpotOrderParams {
+    pub ioc: bool,
+    pub order_type: OrderType,
+    pub side: OrderSide,
+    pub instr_id: InstrId,
+    pub price: i64,
+    pub amount: i64,
+    pub edge_price: i64,
+}
+
+impl TryFrom<&NewSpotOrderData> for NewSpotOrderParams {
+    type Error = ModelsError;
+    fn try_from(data: &NewSpotOrderData) -> Result<Self, Self::Error> {
+        let order_type = OrderType::try_from(data.order_type)?;
+
+        Ok(Self {
+            ioc: data.ioc != 0,
+            order_type,
+            side: OrderSide::try_from(data.side)?,
+            instr_id: data.instr_id,
+            price: check_order_price(order_type, data.price)?,
+            amount: check_qty(data.amount, SPOT_MAX_AMOUNT)?,
+            edge_price: data.edge_price,
+        })
+    }
+}
+
+/// New Perp Order Params
+///
+/// Checked `NewPerpOrderData`, `leverage` is `None` when max possible
+/// leverage is requested
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NewPerpOrderParams {
+    pub ioc: bool,
+    pub leverage: Option<u8>,
+    pub order_type: OrderType,
+    pub side: OrderSide,
+    pub instr_id: InstrId,
+    pub price: i64,
+    pub amount: i64,
+    pub edge_price: i64,
+}
+
+impl TryFrom<&NewPerpOrderData> for NewPerpOrderParams {
+    type Error = ModelsError;
+    fn try_from(data: &NewPerpOrderData) -> Result<Self, Self::Error> {
+        let order_type = OrderType::try_from(data.order_type)?;
+
+        Ok(Self {
+            ioc: data.ioc != 0,
+            leverage: match data.leverage {
+                0 => None,
+                leverage => Some(check_leverage(leverage)?),
+            },
+            order_type,
+            side: OrderSide::try_from(data.side)?,
+            instr_id: data.instr_id,
+            price: check_order_price(order_type, data.price)?,
+            amount: check_qty(data.amount, i64::MAX)?,
+            edge_price: data.edge_price,
+        })
+    }
+}
+
+/// Perp Change Leverage Params
+///
+/// Checked `PerpChangeLeverageData`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PerpChangeLeverageParams {
+    pub leverage: u8,
+    pub instr_id: InstrId,
+}
+
+impl TryFrom<&PerpChangeLeverageData> for PerpChangeLeverageParams {
+    type Error = ModelsError;
+    fn try_from(data: &PerpChangeLeverageData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            leverage: check_leverage(data.leverage)?,
+            instr_id: data.instr_id,
+        })
+    }
+}
+
+/// Swap Params
+///
+/// Checked `SwapData`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SwapParams {
+    /// Sell `asset` if true, else sell `crncy`
+    pub input_asset: bool,
+    pub instr_id: InstrId,
+    pub price: i64,
+    pub amount: i64,
+    pub min_amount_out: i64,
+}
+
+impl TryFrom<&SwapData> for SwapParams {
+    type Error = ModelsError;
+    fn try_from(data: &SwapData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            input_asset: data.input_crncy != 0,
+            instr_id: data.instr_id,
+            price: check_price(data.price)?,
+            amount: check_qty(data.amount, SPOT_MAX_AMOUNT)?,
+            min_amount_out: data.min_amount_out,
+        })
+    }
+}
+
+/// Change SAM Fees Policy Params
+///
+/// Checked `ChangeSAMFeesPolicyData`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChangeSAMFeesPolicyParams {
+    pub sam_fee_type: SAMFeeType,
+    pub instr_id: InstrId,
+    pub fee_rate: f64,
+}
+
+impl TryFrom<&ChangeSAMFeesPolicyData> for ChangeSAMFeesPolicyParams {
+    type Error = ModelsError;
+    fn try_from(data: &ChangeSAMFeesPolicyData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sam_fee_type: SAMFeeType::try_from(data.sam_fee_type)?,
+            instr_id: data.instr_id,
+            fee_rate: data.fee_rate,
+        })
+    }
+}
+
+macro_rules! validate_impl {
+    ($($data:ty => $params:ty),* $(,)?) => {
+        $(
+            impl $data {
+                #[doc = concat!("Converts into checked `", stringify!($params), "`")]
+                pub fn validate(&self) -> Result<$params, ModelsError> {
+                    <$params>::try_from(self)
+                }
+            }
+        )*
+    };
+}
+
+validate_impl! {
+    NewSpotOrderData => NewSpotOrderParams,
+    NewPerpOrderData => NewPerpOrderParams,
+    PerpChangeLeverageData => PerpChangeLeverageParams,
+    SwapData => SwapParams,
+    ChangeSAMFeesPolicyData => ChangeSAMFeesPolicyParams,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction_constants::{DrvInstruction, NewPerpOrderInstruction};
+    use bytemuck::Zeroable;
+
+    fn perp_order() -> NewPerpOrderData {
+        NewPerpOrderData {
+            tag: NewPerpOrderInstruction::INSTRUCTION_NUMBER,
+            order_type: OrderType::Limit as u8,
+            side: 1,
+            instr_id: InstrId(2),
+            price: MIN_PRICE,
+            amount: MIN_QTY,
+            ..NewPerpOrderData::zeroed()
+        }
+    }
+
+    #[test]
+    fn test_new_perp_order_params() {
+        assert_eq!(
+            perp_order().validate(),
+            Ok(NewPerpOrderParams {
+                ioc: false,
+                leverage: None,
+                order_type: OrderType::Limit,
+                side: OrderSide::Ask,
+                instr_id: InstrId(2),
+                price: MIN_PRICE,
+                amount: MIN_QTY,
+                edge_price: 0,
+            })
+        );
+
+        let market = NewPerpOrderData {
+            order_type: OrderType::Market as u8,
+            price: 0,
+            ..perp_order()
+        };
+        assert_eq!(market.validate().map(|params| params.price), Ok(0));
+    }
+
+    #[test]
+    fn test_new_perp_order_errors() {
+        let data = NewPerpOrderData {
+            order_type: 4,
+            ..perp_order()
+        };
+        assert_eq!(
+            data.validate(),
+            Err(ModelsError::UnknownOrderType { order_type_raw: 4 })
+        );
+
+        let data = NewPerpOrderData {
+            leverage: MAX_PERP_LEVERAGE + 1,
+            ..perp_order()
+        };
+        assert_eq!(
+            data.validate(),
+            Err(ModelsError::InvalidLeverage {
+                value: MAX_PERP_LEVERAGE as u32 + 1,
+                max: MAX_PERP_LEVERAGE as u32
+            })
+        );
+
+        let data = NewPerpOrderData {
+            price: MIN_PRICE - 1,
+            ..perp_order()
+        };
+        assert!(matches!(
+            data.validate(),
+            Err(ModelsError::InvalidPrice { .. })
+        ));
+
+        let data = NewPerpOrderData {
+            amount: MIN_QTY - 1,
+            ..perp_order()
+        };
+        assert!(matches!(
+            data.validate(),
+            Err(ModelsError::InvalidQuantity { .. })
+        ));
+
+        let data = NewPerpOrderData {
+            side: 2,
+            ..perp_order()
+        };
+        assert_eq!(data.validate(), Err(ModelsError::InvalidClientDataFormat));
+    }
+
+    #[test]
+    fn test_change_leverage_params() {
+        let data = PerpChangeLeverageData {
+            leverage: 0,
+            ..PerpChangeLeverageData::zeroed()
+        };
+        assert!(matches!(
+            data.validate(),
+            Err(ModelsError::InvalidLeverage { value: 0, .. })
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+//! # JSON module
+//! - Serde helpers used by `log` reports and `instruction_data` payloads
+//! - Raw `u8` flags are rendered by enum name, unknown values stay numeric
+//! - Prices can be rendered as exact decimal strings with the instrument
+//!   `dec_factor`
+
+use serde::{ser::Error, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::{
+    new_types::fixed_point::format_decimal,
+    state::types::{OrderSide, OrderType, SAMFeeType},
+};
+
+/// Fields holding instrument prices
+pub const PRICE_FIELDS: [&str; 4] = ["price", "edge_price", "min_price", "max_price"];
+
+fn by_name<T, S>(value: u8, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: TryFrom<u8> + std::fmt::Debug,
+    S: Serializer,
+{
+    match T::try_from(value) {
+        Ok(value) => serializer.serialize_str(&format!("{:?}", value)),
+        Err(_) => serializer.serialize_u8(value),
+    }
+}
+
+pub fn order_side<S: Serializer>(value: &u8, serializer: S) -> Result<S::Ok, S::Error> {
+    by_name::<OrderSide, S>(*value, serializer)
+}
+
+pub fn order_type<S: Serializer>(value: &u8, serializer: S) -> Result<S::Ok, S::Error> {
+    by_name::<OrderType, S>(*value, serializer)
+}
+
+pub fn sam_fee_type<S: Serializer>(value: &u8, serializer: S) -> Result<S::Ok, S::Error> {
+    by_name::<SAMFeeType, S>(*value, serializer)
+}
+
+/// Serializes `value` with `PRICE_FIELDS` divided by `dec_factor`
+///
+/// Prices are rendered as decimal strings, so no precision is lost,
+/// `dec_factor` must be a power of ten
+pub fn to_json_with_dec_factor<T: Serialize>(
+    value: &T,
+    dec_factor: i64,
+) -> serde_json::Result<Value> {
+    let decimals = dec_factor
+        .checked_ilog10()
+        .filter(|decimals| 10i64.pow(*decimals) == dec_factor)
+        .ok_or_else(|| {
+            serde_json::Error::custom(format!("dec_factor {} is not a power of ten", dec_factor))
+        })?;
+
+    let mut json = serde_json::to_value(value)?;
+
+    if let Value::Object(fields) = &mut json {
+        for name in PRICE_FIELDS {
+            if let Some(field) = fields.get_mut(name) {
+                if let Some(price) = field.as_i64() {
+                    *field = Value::from(format_decimal(price, decimals));
+                }
+            }
+        }
+    }
+
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        log::{log_type, DepositReport, SpotPlaceOrderReport},
+        new_types::{client::ClientId, instrument::InstrId},
+    };
+
+    #[test]
+    fn test_report_json() {
+        let report = SpotPlaceOrderReport {
+            tag: log_type::SPOT_PLACE_ORDER,
+            ioc: 0,
+            side: 1,
+            order_type: 7,
+            client_id: ClientId(5),
+            order_id: 10,
+            qty: 20,
+            price: 1_500,
+            instr_id: InstrId(3),
+            time: 100,
+        };
+
+        assert_eq!(
+            serde_json::to_value(report).ok(),
+            Some(serde_json::json!({
+                "tag": 10,
+                "ioc": 0,
+                "side": "Ask",
+                "order_type": 7,
+                "client_id": 5,
+                "order_id": 10,
+                "qty": 20,
+                "price": 1_500,
+                "instr_id": 3,
+                "time": 100,
+            }))
+        );
+
+        assert_eq!(
+            to_json_with_dec_factor(&report, 1_000)
+                .ok()
+                .and_then(|json| json["price"].as_str().map(str::to_owned)),
+            Some("1.5".to_string())
+        );
+
+        let report = SpotPlaceOrderReport {
+            price: 123_456_789_123_456_789,
+            ..report
+        };
+        assert_eq!(
+            to_json_with_dec_factor(&report, 1_000_000_000)
+                .ok()
+                .and_then(|json| json["price"].as_str().map(str::to_owned)),
+            Some("123456789.123456789".to_string())
+        );
+        assert!(to_json_with_dec_factor(&report, 3).is_err());
+
+        assert!(serde_json::to_value(DepositReport::default())
+            .is_ok_and(|json| json.get("padding_u8").is_none()));
+    }
+}
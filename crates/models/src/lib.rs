@@ -1,6 +1,13 @@
 pub mod constants;
+pub mod cu_stats;
+pub mod errors;
+pub mod instruction;
+pub mod instruction_builder;
 pub mod instruction_constants;
 pub mod instruction_data;
+pub mod instruction_params;
+#[cfg(feature = "serde")]
+pub mod json;
 pub mod log;
 pub mod new_types;
 pub mod state;
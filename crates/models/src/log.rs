@@ -1,5 +1,17 @@
-use crate::new_types::{client::ClientId, instrument::InstrId};
+use crate::{
+    constants::candles::{CandleParams, CANDLES},
+    errors::ModelsError,
+    instruction::read_payload,
+    new_types::{
+        client::ClientId,
+        fixed_point::{div_round, Rounding},
+        instrument::InstrId,
+    },
+    state::candles::Candle,
+};
 use bytemuck::{Pod, Zeroable};
+use drv_macros::DrvLog;
+use std::mem::size_of;
 
 pub mod log_type {
     pub const DEPOSIT: u8 = 1;
@@ -51,20 +63,27 @@ pub mod log_type {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::PERP_LOSS_COVERAGE)]
 pub struct PerpLossCoverageReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub loss_coverage: i64,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::PERP_CHANGE_LEVERAGE)]
 pub struct PerpChangeLeverageReport {
     pub tag: u8,
     pub leverage: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub instr_id: InstrId,
@@ -72,22 +91,31 @@ pub struct PerpChangeLeverageReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::DRVS_AIRDROP)]
 pub struct DrvsAirdropReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub amount: i64,
     pub time: u32,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::EARNINGS)]
 pub struct EarningsReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub token_id: u32,
@@ -96,10 +124,14 @@ pub struct EarningsReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::DEPOSIT)]
 pub struct DepositReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub token_id: u32,
@@ -109,10 +141,14 @@ pub struct DepositReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::FEES_DEPOSIT)]
 pub struct FeesDepositReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub token_id: u32,
@@ -121,10 +157,14 @@ pub struct FeesDepositReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::FEES_WITHDRAW)]
 pub struct FeesWithdrawReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub token_id: u32,
@@ -133,10 +173,14 @@ pub struct FeesWithdrawReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::PERP_DEPOSIT)]
 pub struct PerpDepositReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub instr_id: InstrId,
@@ -145,10 +189,14 @@ pub struct PerpDepositReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::BUY_MARKET_SEAT)]
 pub struct BuyMarketSeatReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub instr_id: InstrId,
@@ -158,10 +206,14 @@ pub struct BuyMarketSeatReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::SELL_MARKET_SEAT)]
 pub struct SellMarketSeatReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub instr_id: InstrId,
@@ -170,10 +222,14 @@ pub struct SellMarketSeatReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::WITHDRAW)]
 pub struct WithdrawReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub token_id: u32,
@@ -183,10 +239,14 @@ pub struct WithdrawReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::PERP_WITHDRAW)]
 pub struct PerpWithdrawReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub instr_id: InstrId,
@@ -195,10 +255,14 @@ pub struct PerpWithdrawReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::SPOT_LP_TRADE)]
 pub struct SpotlpTradeReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub time: u32,
@@ -210,10 +274,14 @@ pub struct SpotlpTradeReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::PERP_FILL_ORDER)]
 pub struct PerpFillOrderReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub order_id: i64,
@@ -224,10 +292,14 @@ pub struct PerpFillOrderReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::SPOT_FILL_ORDER)]
 pub struct SpotFillOrderReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub order_id: i64,
@@ -238,11 +310,15 @@ pub struct SpotFillOrderReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::PERP_PLACE_ORDER)]
 pub struct PerpPlaceOrderReport {
     pub tag: u8,
     pub ioc: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_type"))]
     pub order_type: u8,
     pub client_id: ClientId,
     pub order_id: i64,
@@ -251,15 +327,20 @@ pub struct PerpPlaceOrderReport {
     pub instr_id: InstrId,
     pub leverage: u32,
     pub time: u32,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::SPOT_PLACE_ORDER)]
 pub struct SpotPlaceOrderReport {
     pub tag: u8,
     pub ioc: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_type"))]
     pub order_type: u8,
     pub client_id: ClientId,
     pub order_id: i64,
@@ -270,12 +351,18 @@ pub struct SpotPlaceOrderReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default, Debug)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, Debug, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::SWAP_ORDER)]
 pub struct PlaceSwapOrderReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_type"))]
     pub order_type: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
     pub order_id: i64,
     pub qty: i64,
@@ -314,10 +401,14 @@ pub struct PlaceSwapOrderReport {
 // }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::PERP_PLACE_MASS_CANCEL)]
 pub struct PerpPlaceMassCancelReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub instr_id: InstrId,
@@ -325,10 +416,14 @@ pub struct PerpPlaceMassCancelReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::SPOT_PLACE_MASS_CANCEL)]
 pub struct SpotPlaceMassCancelReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub instr_id: InstrId,
@@ -336,11 +431,16 @@ pub struct SpotPlaceMassCancelReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::PERP_MASS_CANCEL)]
 pub struct PerpMassCancelReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
     pub order_id: i64,
     pub perps: i64,
@@ -348,11 +448,16 @@ pub struct PerpMassCancelReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::SPOT_MASS_CANCEL)]
 pub struct SpotMassCancelReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
     pub order_id: i64,
     pub qty: i64,
@@ -360,10 +465,14 @@ pub struct SpotMassCancelReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::PERP_FEES)]
 pub struct PerpFeesReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub ref_client_id: ClientId,
     pub fees: i64,
@@ -371,10 +480,14 @@ pub struct PerpFeesReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::SPOT_FEES)]
 pub struct SpotFeesReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub ref_client_id: ClientId,
     pub fees: i64,
@@ -382,10 +495,14 @@ pub struct SpotFeesReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::PERP_FUNDING)]
 pub struct PerpFundingReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub instr_id: InstrId,
@@ -394,10 +511,14 @@ pub struct PerpFundingReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::PERP_SOC_LOSS)]
 pub struct PerpSocLossReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub instr_id: InstrId,
@@ -406,32 +527,46 @@ pub struct PerpSocLossReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::PERP_NEW_ORDER)]
 pub struct PerpNewOrderReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
     pub perps: i64,
     pub crncy: i64,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::SPOT_NEW_ORDER)]
 pub struct SpotNewOrderReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
     pub qty: i64,
     pub crncy: i64,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::PERP_ORDER_CANCEL)]
 pub struct PerpOrderCancelReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub instr_id: InstrId,
@@ -442,10 +577,14 @@ pub struct PerpOrderCancelReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::SPOT_ORDER_CANCEL)]
 pub struct SpotOrderCancelReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub instr_id: InstrId,
@@ -456,10 +595,14 @@ pub struct SpotOrderCancelReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::PERP_ORDER_REVOKE)]
 pub struct PerpOrderRevokeReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub order_id: i64,
@@ -468,10 +611,14 @@ pub struct PerpOrderRevokeReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::SPOT_ORDER_REVOKE)]
 pub struct SpotOrderRevokeReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::json::order_side"))]
     pub side: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub order_id: i64,
@@ -480,10 +627,14 @@ pub struct SpotOrderRevokeReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::MOVE_SPOT)]
 pub struct MoveSpotAvailFundsReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub instr_id: InstrId,
@@ -493,10 +644,14 @@ pub struct MoveSpotAvailFundsReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::CHANGED_POINTS)]
 pub struct ChangePointsRecord {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub points: u32,
@@ -504,91 +659,129 @@ pub struct ChangePointsRecord {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::SWAP_FEES)]
 pub struct SwapRefFeesReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u32: u32,
     pub fees: i64,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::VM_INIT_ACTIVATE)]
 pub struct VmInitActivateReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub time: u32,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding: u32,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::VM_INIT_ACTIVATE_CANCEL)]
 pub struct VmInitActivateCancelReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub time: u32,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::VM_FINALIZE_ACTIVATE)]
 pub struct VmFinalizeActivateReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub time: u32,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::VM_INIT_DEACTIVATE)]
 pub struct VmInitDeactivateReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub time: u32,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::VM_INIT_DEACTIVATE_CANCEL)]
 pub struct VmInitDeactivateCancelReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub time: u32,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::VM_FINALIZE_DEACTIVATE)]
 pub struct VmFinalizeDeactivateReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub time: u32,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::VM_CHANGE_LIST)]
 pub struct VmChangeListReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub time: u32,
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::VM_INIT_WITHDRAW)]
 pub struct VmInitWithdrawReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub token_id: u32,
@@ -597,10 +790,14 @@ pub struct VmInitWithdrawReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::VM_INIT_WITHDRAW_CANCEL)]
 pub struct VmInitWithdrawCancelReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub token_id: u32,
@@ -608,13 +805,740 @@ pub struct VmInitWithdrawCancelReport {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable, Pod, Default)]
+#[derive(Copy, Clone, Zeroable, Pod, Default, DrvLog)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[drv_log(tag = log_type::VM_INIT_WITHDRAW_FINALIZE)]
 pub struct VmInitWithdrawFinalizeReport {
     pub tag: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u8: u8,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub padding_u16: u16,
     pub client_id: ClientId,
     pub token_id: u32,
     pub time: u32,
     pub amount: i64,
 }
+
+macro_rules! log_events {
+    ($($variant:ident($log_type:ident, $report:ty)),* $(,)?) => {
+        $(
+            const _: () = assert!(
+                <$report>::TAG == log_type::$log_type,
+                concat!(stringify!($report), " is bound to another tag than ", stringify!($log_type)),
+            );
+        )*
+
+        /// Log Event
+        ///
+        /// One variant per report emitted by the program, keyed by the
+        /// leading `log_type` tag byte. `NEW_PRIVATE_CLIENT` has no report
+        /// struct and is not decoded.
+        #[derive(Clone, Copy)]
+        pub enum LogEvent {
+            $($variant($report),)*
+        }
+
+        impl LogEvent {
+            /// Size of the report with the given tag
+            pub const fn report_size(tag: u8) -> Option<usize> {
+                match tag {
+                    $(log_type::$log_type => Some(size_of::<$report>()),)*
+                    _ => None,
+                }
+            }
+
+            /// Decodes a single report by its tag byte, trailing bytes are ignored
+            ///
+            /// ### Errors
+            /// - `InvalidDataLength` - data is empty or shorter than the report
+            /// - `InvalidClientDataFormat` - unknown tag
+            pub fn decode(data: &[u8]) -> Result<Self, ModelsError> {
+                let tag = *data.first().ok_or(ModelsError::InvalidDataLength {
+                    expected: 1,
+                    actual: 0,
+                })?;
+
+                Ok(match tag {
+                    $(log_type::$log_type => Self::$variant(read_payload::<$report>(data)?),)*
+                    _ => return Err(ModelsError::InvalidClientDataFormat),
+                })
+            }
+
+            /// Tag byte of the report, equals one of `log_type` constants
+            pub const fn tag(&self) -> u8 {
+                match self {
+                    $(Self::$variant(_) => log_type::$log_type,)*
+                }
+            }
+        }
+    };
+}
+
+log_events! {
+    Deposit(DEPOSIT, DepositReport),
+    Withdraw(WITHDRAW, WithdrawReport),
+    PerpDeposit(PERP_DEPOSIT, PerpDepositReport),
+    PerpWithdraw(PERP_WITHDRAW, PerpWithdrawReport),
+    FeesDeposit(FEES_DEPOSIT, FeesDepositReport),
+    FeesWithdraw(FEES_WITHDRAW, FeesWithdrawReport),
+    SpotLpTrade(SPOT_LP_TRADE, SpotlpTradeReport),
+    Earnings(EARNINGS, EarningsReport),
+    DrvsAirdrop(DRVS_AIRDROP, DrvsAirdropReport),
+    SpotPlaceOrder(SPOT_PLACE_ORDER, SpotPlaceOrderReport),
+    SpotFillOrder(SPOT_FILL_ORDER, SpotFillOrderReport),
+    SpotNewOrder(SPOT_NEW_ORDER, SpotNewOrderReport),
+    SpotOrderCancel(SPOT_ORDER_CANCEL, SpotOrderCancelReport),
+    SpotOrderRevoke(SPOT_ORDER_REVOKE, SpotOrderRevokeReport),
+    SpotFees(SPOT_FEES, SpotFeesReport),
+    SpotPlaceMassCancel(SPOT_PLACE_MASS_CANCEL, SpotPlaceMassCancelReport),
+    SpotMassCancel(SPOT_MASS_CANCEL, SpotMassCancelReport),
+    PerpPlaceOrder(PERP_PLACE_ORDER, PerpPlaceOrderReport),
+    PerpFillOrder(PERP_FILL_ORDER, PerpFillOrderReport),
+    PerpNewOrder(PERP_NEW_ORDER, PerpNewOrderReport),
+    PerpOrderCancel(PERP_ORDER_CANCEL, PerpOrderCancelReport),
+    PerpOrderRevoke(PERP_ORDER_REVOKE, PerpOrderRevokeReport),
+    PerpFees(PERP_FEES, PerpFeesReport),
+    PerpFunding(PERP_FUNDING, PerpFundingReport),
+    PerpPlaceMassCancel(PERP_PLACE_MASS_CANCEL, PerpPlaceMassCancelReport),
+    PerpMassCancel(PERP_MASS_CANCEL, PerpMassCancelReport),
+    PerpSocLoss(PERP_SOC_LOSS, PerpSocLossReport),
+    PerpChangeLeverage(PERP_CHANGE_LEVERAGE, PerpChangeLeverageReport),
+    BuyMarketSeat(BUY_MARKET_SEAT, BuyMarketSeatReport),
+    SellMarketSeat(SELL_MARKET_SEAT, SellMarketSeatReport),
+    SwapOrder(SWAP_ORDER, PlaceSwapOrderReport),
+    MoveSpot(MOVE_SPOT, MoveSpotAvailFundsReport),
+    ChangedPoints(CHANGED_POINTS, ChangePointsRecord),
+    SwapFees(SWAP_FEES, SwapRefFeesReport),
+    VmInitActivate(VM_INIT_ACTIVATE, VmInitActivateReport),
+    VmInitActivateCancel(VM_INIT_ACTIVATE_CANCEL, VmInitActivateCancelReport),
+    VmFinalizeActivate(VM_FINALIZE_ACTIVATE, VmFinalizeActivateReport),
+    VmInitDeactivate(VM_INIT_DEACTIVATE, VmInitDeactivateReport),
+    VmInitDeactivateCancel(VM_INIT_DEACTIVATE_CANCEL, VmInitDeactivateCancelReport),
+    VmFinalizeDeactivate(VM_FINALIZE_DEACTIVATE, VmFinalizeDeactivateReport),
+    VmChangeList(VM_CHANGE_LIST, VmChangeListReport),
+    VmInitWithdraw(VM_INIT_WITHDRAW, VmInitWithdrawReport),
+    VmInitWithdrawCancel(VM_INIT_WITHDRAW_CANCEL, VmInitWithdrawCancelReport),
+    VmInitWithdrawFinalize(VM_INIT_WITHDRAW_FINALIZE, VmInitWithdrawFinalizeReport),
+    PerpLossCoverage(PERP_LOSS_COVERAGE, PerpLossCoverageReport),
+}
+
+/// Iterator over reports concatenated in a single log buffer
+///
+/// Stops after the first error, since the size of an undecodable report
+/// is unknown.
+pub struct LogEvents<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> LogEvents<'a> {
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+}
+
+impl Iterator for LogEvents<'_> {
+    type Item = Result<LogEvent, ModelsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tag = *self.data.first()?;
+
+        let event = LogEvent::decode(self.data);
+        self.data = match (&event, LogEvent::report_size(tag)) {
+            (Ok(_), Some(size)) => &self.data[size..],
+            _ => &[],
+        };
+
+        Some(event)
+    }
+}
+
+/// Order Execution
+///
+/// Reports emitted by a single order placement
+///
+/// 1. **`placed`** - Place order report opening the lifecycle
+/// 2. **`fills`** - Fills against resting orders in emission order
+/// 3. **`fees`** - Fees paid by the taker
+/// 4. **`resting_remainder`** - Remainder put on the order book
+/// 5. **`revoked`** - Orders revoked while the order was processed
+#[derive(Clone, Default)]
+pub struct OrderExecution<Place, Fill, Fees, New, Revoke> {
+    pub placed: Place,
+    pub fills: Vec<Fill>,
+    pub fees: Vec<Fees>,
+    pub resting_remainder: Option<New>,
+    pub revoked: Vec<Revoke>,
+}
+
+impl<Place, Fill, Fees, New, Revoke> OrderExecution<Place, Fill, Fees, New, Revoke> {
+    const fn new(placed: Place) -> Self {
+        Self {
+            placed,
+            fills: Vec::new(),
+            fees: Vec::new(),
+            resting_remainder: None,
+            revoked: Vec::new(),
+        }
+    }
+}
+
+pub type SpotOrderExecution = OrderExecution<
+    SpotPlaceOrderReport,
+    SpotFillOrderReport,
+    SpotFeesReport,
+    SpotNewOrderReport,
+    SpotOrderRevokeReport,
+>;
+
+pub type PerpOrderExecution = OrderExecution<
+    PerpPlaceOrderReport,
+    PerpFillOrderReport,
+    PerpFeesReport,
+    PerpNewOrderReport,
+    PerpOrderRevokeReport,
+>;
+
+pub type SwapOrderExecution = OrderExecution<
+    PlaceSwapOrderReport,
+    SpotFillOrderReport,
+    SwapRefFeesReport,
+    SpotNewOrderReport,
+    SpotOrderRevokeReport,
+>;
+
+#[derive(Clone)]
+pub enum MarketOrderExecution {
+    Spot(SpotOrderExecution),
+    Perp(PerpOrderExecution),
+    /// Swap fills resting spot orders, it never leaves a remainder
+    Swap(SwapOrderExecution),
+    /// Order event without an execution to attach to: fill, fees, new order
+    /// or revoke report of another kind than the current execution or
+    /// without one, mass cancel placement
+    Unmatched(LogEvent),
+}
+
+/// Executions closed by one event, followed by the event itself if it is
+/// unmatched
+pub type ClosedExecutions = std::iter::Chain<
+    std::option::IntoIter<MarketOrderExecution>,
+    std::option::IntoIter<MarketOrderExecution>,
+>;
+
+/// Order Lifecycle Assembler
+///
+/// Groups decoded events of a transaction into `MarketOrderExecution`
+/// records. Events are expected in emission order: a place order or swap
+/// report opens an execution, following fill, fees, new order and revoke
+/// reports of the same kind are attached to it. Any other placement closes
+/// the current execution. Order events which can not be attached are
+/// returned as `Unmatched`, events unrelated to orders are skipped.
+#[derive(Clone, Default)]
+pub struct OrderLifecycleAssembler {
+    current: Option<MarketOrderExecution>,
+}
+
+impl OrderLifecycleAssembler {
+    pub const fn new() -> Self {
+        Self { current: None }
+    }
+
+    /// Consumes next event, returns an execution closed by it and the event
+    /// itself if it is unmatched
+    pub fn push(&mut self, event: LogEvent) -> ClosedExecutions {
+        let (closed, unmatched) = match (&mut self.current, event) {
+            (_, LogEvent::SpotPlaceOrder(report)) => (
+                self.current
+                    .replace(MarketOrderExecution::Spot(OrderExecution::new(report))),
+                None,
+            ),
+            (_, LogEvent::PerpPlaceOrder(report)) => (
+                self.current
+                    .replace(MarketOrderExecution::Perp(OrderExecution::new(report))),
+                None,
+            ),
+            (_, LogEvent::SwapOrder(report)) => (
+                self.current
+                    .replace(MarketOrderExecution::Swap(OrderExecution::new(report))),
+                None,
+            ),
+            (_, LogEvent::SpotPlaceMassCancel(_) | LogEvent::PerpPlaceMassCancel(_)) => {
+                (self.current.take(), Some(event))
+            }
+            (Some(MarketOrderExecution::Spot(execution)), LogEvent::SpotFillOrder(report)) => {
+                execution.fills.push(report);
+                (None, None)
+            }
+            (Some(MarketOrderExecution::Spot(execution)), LogEvent::SpotFees(report)) => {
+                execution.fees.push(report);
+                (None, None)
+            }
+            (Some(MarketOrderExecution::Spot(execution)), LogEvent::SpotNewOrder(report)) => {
+                execution.resting_remainder = Some(report);
+                (None, None)
+            }
+            (Some(MarketOrderExecution::Spot(execution)), LogEvent::SpotOrderRevoke(report)) => {
+                execution.revoked.push(report);
+                (None, None)
+            }
+            (Some(MarketOrderExecution::Perp(execution)), LogEvent::PerpFillOrder(report)) => {
+                execution.fills.push(report);
+                (None, None)
+            }
+            (Some(MarketOrderExecution::Perp(execution)), LogEvent::PerpFees(report)) => {
+                execution.fees.push(report);
+                (None, None)
+            }
+            (Some(MarketOrderExecution::Perp(execution)), LogEvent::PerpNewOrder(report)) => {
+                execution.resting_remainder = Some(report);
+                (None, None)
+            }
+            (Some(MarketOrderExecution::Perp(execution)), LogEvent::PerpOrderRevoke(report)) => {
+                execution.revoked.push(report);
+                (None, None)
+            }
+            (Some(MarketOrderExecution::Swap(execution)), LogEvent::SpotFillOrder(report)) => {
+                execution.fills.push(report);
+                (None, None)
+            }
+            (Some(MarketOrderExecution::Swap(execution)), LogEvent::SwapFees(report)) => {
+                execution.fees.push(report);
+                (None, None)
+            }
+            (Some(MarketOrderExecution::Swap(execution)), LogEvent::SpotOrderRevoke(report)) => {
+                execution.revoked.push(report);
+                (None, None)
+            }
+            (
+                _,
+                LogEvent::SpotFillOrder(_)
+                | LogEvent::SpotFees(_)
+                | LogEvent::SpotNewOrder(_)
+                | LogEvent::SpotOrderRevoke(_)
+                | LogEvent::PerpFillOrder(_)
+                | LogEvent::PerpFees(_)
+                | LogEvent::PerpNewOrder(_)
+                | LogEvent::PerpOrderRevoke(_)
+                | LogEvent::SwapFees(_),
+            ) => (None, Some(event)),
+            _ => (None, None),
+        };
+
+        closed
+            .into_iter()
+            .chain(unmatched.map(MarketOrderExecution::Unmatched))
+    }
+
+    /// Closes the current execution, called at the end of a transaction
+    pub const fn finish(&mut self) -> Option<MarketOrderExecution> {
+        self.current.take()
+    }
+
+    /// Assembles all executions of a transaction
+    pub fn assemble(events: impl IntoIterator<Item = LogEvent>) -> Vec<MarketOrderExecution> {
+        let mut assembler = Self::new();
+        let mut executions = events
+            .into_iter()
+            .flat_map(|event| assembler.push(event))
+            .collect::<Vec<_>>();
+        executions.extend(assembler.finish());
+
+        executions
+    }
+}
+
+/// Candle Trade
+///
+/// Price and traded volumes of one fill applied to candles
+///
+/// 1. **`time`** - Unix time of the transaction
+/// 2. **`price`** - Trade price
+/// 3. **`asset_tokens`** - Traded asset tokens (perps for perp fills)
+/// 4. **`crncy_tokens`** - Traded crncy tokens
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CandleTrade {
+    pub time: u32,
+    pub price: i64,
+    pub asset_tokens: i64,
+    pub crncy_tokens: i64,
+}
+
+impl CandleTrade {
+    /// Fill reports carry no time, `time` is taken from the preceding place
+    /// order or swap report
+    pub const fn from_spot_fill(report: &SpotFillOrderReport, time: u32) -> Self {
+        Self {
+            time,
+            price: report.price,
+            asset_tokens: report.qty,
+            crncy_tokens: report.crncy,
+        }
+    }
+
+    pub const fn from_perp_fill(report: &PerpFillOrderReport, time: u32) -> Self {
+        Self {
+            time,
+            price: report.price,
+            asset_tokens: report.perps,
+            crncy_tokens: report.crncy,
+        }
+    }
+
+    /// LP trades are priced as `crncy * dec_factor / tokens`, `None` if no
+    /// asset tokens were traded or price does not fit `i64`
+    pub fn from_spot_lp_trade(report: &SpotlpTradeReport, dec_factor: i64) -> Option<Self> {
+        let price = div_round(
+            i128::from(report.crncy) * i128::from(dec_factor),
+            i128::from(report.tokens),
+            Rounding::TowardZero,
+        )
+        .ok()?;
+
+        Some(Self {
+            time: report.time,
+            price,
+            asset_tokens: report.tokens,
+            crncy_tokens: report.crncy,
+        })
+    }
+
+    const fn candle(&self, duration: u32) -> Candle {
+        Candle {
+            asset_tokens: self.asset_tokens,
+            crncy_tokens: self.crncy_tokens,
+            counter: 1,
+            ..Candle::flat(self.price, self.time - self.time % duration)
+        }
+    }
+}
+
+/// Candle Aggregator
+///
+/// Builds candles of every `CANDLES` timeframe from trades of one instrument.
+/// Each trade opens a candle aligned to `time % duration == 0` or extends the
+/// last one: close is the trade price, max and min are widened, volumes are
+/// added and `counter` is incremented. Trades are expected in chronological
+/// order, trades older than the last candle are merged into it.
+#[derive(Clone, Debug)]
+pub struct CandleAggregator {
+    dec_factor: i64,
+    time: Option<u32>,
+    untimed_fills: u32,
+    series: Vec<(CandleParams, Vec<Candle>)>,
+}
+
+impl CandleAggregator {
+    /// `dec_factor` of the instrument prices LP trades
+    pub fn new(dec_factor: i64) -> Self {
+        Self {
+            dec_factor,
+            time: None,
+            untimed_fills: 0,
+            series: CANDLES.iter().map(|params| (*params, Vec::new())).collect(),
+        }
+    }
+
+    pub fn push(&mut self, trade: CandleTrade) {
+        for (params, candles) in &mut self.series {
+            let candle = trade.candle(params.duration);
+
+            match candles.last_mut() {
+                Some(last) if last.time >= candle.time => last.merge(&candle),
+                _ => candles.push(candle),
+            }
+        }
+    }
+
+    /// Consumes next spot event in emission order
+    ///
+    /// Spot fills are timed by the last place order or swap report, fills
+    /// seen before any of them are counted in `untimed_fills` and skipped.
+    /// `CANDLES` are spot candles accounts, perp events are ignored, perp
+    /// fills can be aggregated separately with `CandleTrade::from_perp_fill`
+    /// and `push`.
+    pub fn push_event(&mut self, event: &LogEvent) {
+        match event {
+            LogEvent::SpotPlaceOrder(report) => self.time = Some(report.time),
+            LogEvent::SwapOrder(report) => self.time = Some(report.time),
+            LogEvent::SpotFillOrder(report) => match self.time {
+                Some(time) => self.push(CandleTrade::from_spot_fill(report, time)),
+                None => self.untimed_fills += 1,
+            },
+            LogEvent::SpotLpTrade(report) => {
+                if let Some(trade) = CandleTrade::from_spot_lp_trade(report, self.dec_factor) {
+                    self.push(trade);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Amount of spot fills skipped because no place order or swap report
+    /// preceded them
+    pub const fn untimed_fills(&self) -> u32 {
+        self.untimed_fills
+    }
+
+    /// Chronological candles of the timeframe with candles account `tag`
+    pub fn candles(&self, tag: u32) -> Option<&[Candle]> {
+        self.series
+            .iter()
+            .find(|(params, _)| params.tag == tag)
+            .map(|(_, candles)| candles.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_events() {
+        let deposit = DepositReport {
+            tag: log_type::DEPOSIT,
+            client_id: ClientId(1),
+            amount: 100,
+            ..Default::default()
+        };
+        let fees = SpotFeesReport {
+            tag: log_type::SPOT_FEES,
+            ..Default::default()
+        };
+
+        let mut buffer = vec![0u8];
+        buffer.extend_from_slice(bytemuck::bytes_of(&deposit));
+        buffer.extend_from_slice(bytemuck::bytes_of(&fees));
+
+        let events = LogEvents::new(&buffer[1..]).collect::<Vec<_>>();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            events[0],
+            Ok(LogEvent::Deposit(DepositReport { amount: 100, .. }))
+        ));
+        assert_eq!(
+            events[1].as_ref().map(|event| event.tag()),
+            Ok(log_type::SPOT_FEES)
+        );
+    }
+
+    #[test]
+    fn test_log_events_errors() {
+        let mut events = LogEvents::new(&[log_type::NEW_PRIVATE_CLIENT, 0, 0, 0]);
+        assert!(matches!(
+            events.next(),
+            Some(Err(ModelsError::InvalidClientDataFormat))
+        ));
+        assert!(events.next().is_none());
+
+        assert_eq!(
+            LogEvent::decode(&[log_type::DEPOSIT]).err(),
+            Some(ModelsError::InvalidDataLength {
+                expected: size_of::<DepositReport>(),
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_drv_log() {
+        let deposit = DepositReport::new(ClientId(1), 2, 5, 100, 7);
+        assert_eq!(deposit.tag, log_type::DEPOSIT);
+        assert_eq!(
+            deposit.to_string(),
+            "DepositReport { tag: 1, client_id: ClientId(1), token_id: 2, time: 5, amount: 100, custom_id: 7 }"
+        );
+
+        let bytes = bytemuck::bytes_of(&deposit);
+        assert!(matches!(
+            DepositReport::from_bytes(bytes),
+            Ok(DepositReport { amount: 100, .. })
+        ));
+        assert_eq!(
+            DepositReport::from_bytes(&bytes[1..]).err(),
+            Some(ModelsError::InvalidDataLength {
+                expected: size_of::<DepositReport>(),
+                actual: size_of::<DepositReport>() - 1
+            })
+        );
+
+        let mut bytes = bytes.to_vec();
+        bytes[0] = log_type::WITHDRAW;
+        assert_eq!(
+            DepositReport::from_bytes(&bytes).err(),
+            Some(ModelsError::InvalidClientDataFormat)
+        );
+    }
+
+    #[test]
+    fn test_order_lifecycle_assembler() {
+        let place = |order_id| {
+            LogEvent::SpotPlaceOrder(SpotPlaceOrderReport {
+                tag: log_type::SPOT_PLACE_ORDER,
+                order_id,
+                ..Default::default()
+            })
+        };
+        let fill = |qty| {
+            LogEvent::SpotFillOrder(SpotFillOrderReport {
+                tag: log_type::SPOT_FILL_ORDER,
+                qty,
+                ..Default::default()
+            })
+        };
+
+        let executions = OrderLifecycleAssembler::assemble([
+            place(1),
+            fill(10),
+            fill(20),
+            LogEvent::SpotFees(SpotFeesReport {
+                tag: log_type::SPOT_FEES,
+                fees: 3,
+                ..Default::default()
+            }),
+            LogEvent::SpotNewOrder(SpotNewOrderReport {
+                tag: log_type::SPOT_NEW_ORDER,
+                qty: 5,
+                ..Default::default()
+            }),
+            place(2),
+            LogEvent::SwapOrder(PlaceSwapOrderReport::default()),
+            fill(30),
+            LogEvent::PerpFillOrder(PerpFillOrderReport::default()),
+            LogEvent::SpotPlaceMassCancel(SpotPlaceMassCancelReport::default()),
+            fill(40),
+        ]);
+
+        assert_eq!(executions.len(), 6);
+        match &executions[0] {
+            MarketOrderExecution::Spot(execution) => {
+                assert_eq!(execution.placed.order_id, 1);
+                assert_eq!(
+                    execution
+                        .fills
+                        .iter()
+                        .map(|fill| fill.qty)
+                        .collect::<Vec<_>>(),
+                    vec![10, 20]
+                );
+                assert_eq!(execution.fees.len(), 1);
+                assert_eq!(execution.resting_remainder.map(|order| order.qty), Some(5));
+                assert!(execution.revoked.is_empty());
+            }
+            _ => panic!("Expected spot execution"),
+        }
+        assert!(matches!(
+            &executions[1],
+            MarketOrderExecution::Spot(execution) if execution.placed.order_id == 2 && execution.fills.is_empty()
+        ));
+        assert!(matches!(
+            &executions[2],
+            MarketOrderExecution::Unmatched(LogEvent::PerpFillOrder(_))
+        ));
+        assert!(matches!(
+            &executions[3],
+            MarketOrderExecution::Swap(execution) if execution.fills.len() == 1 && execution.fills[0].qty == 30
+        ));
+        assert!(matches!(
+            &executions[4],
+            MarketOrderExecution::Unmatched(LogEvent::SpotPlaceMassCancel(_))
+        ));
+        assert!(matches!(
+            &executions[5],
+            MarketOrderExecution::Unmatched(LogEvent::SpotFillOrder(report)) if report.qty == 40
+        ));
+    }
+
+    #[test]
+    fn test_candle_aggregator() {
+        use crate::state::types::account_type::{SPOT_15M_CANDLES, SPOT_1M_CANDLES};
+
+        let fill = |qty, price| {
+            LogEvent::SpotFillOrder(SpotFillOrderReport {
+                tag: log_type::SPOT_FILL_ORDER,
+                qty,
+                crncy: qty * price,
+                price,
+                ..Default::default()
+            })
+        };
+        let place = |time| {
+            LogEvent::SpotPlaceOrder(SpotPlaceOrderReport {
+                tag: log_type::SPOT_PLACE_ORDER,
+                time,
+                ..Default::default()
+            })
+        };
+
+        let mut aggregator = CandleAggregator::new(1);
+        for event in [
+            fill(1, 1),
+            place(125),
+            fill(2, 10),
+            fill(1, 12),
+            LogEvent::SwapOrder(PlaceSwapOrderReport {
+                time: 190,
+                ..Default::default()
+            }),
+            fill(3, 8),
+            LogEvent::SpotLpTrade(SpotlpTradeReport {
+                tag: log_type::SPOT_LP_TRADE,
+                time: 200,
+                tokens: 4,
+                crncy: 36,
+                ..Default::default()
+            }),
+        ] {
+            aggregator.push_event(&event);
+        }
+
+        assert_eq!(
+            aggregator.candles(SPOT_1M_CANDLES),
+            Some(
+                &[
+                    Candle {
+                        open: 10,
+                        close: 12,
+                        max: 12,
+                        min: 10,
+                        asset_tokens: 3,
+                        crncy_tokens: 32,
+                        time: 120,
+                        counter: 2,
+                    },
+                    Candle {
+                        open: 8,
+                        close: 9,
+                        max: 9,
+                        min: 8,
+                        asset_tokens: 7,
+                        crncy_tokens: 60,
+                        time: 120 + 60,
+                        counter: 2,
+                    },
+                ][..]
+            )
+        );
+        assert_eq!(
+            aggregator.candles(SPOT_15M_CANDLES),
+            Some(
+                &[Candle {
+                    open: 10,
+                    close: 9,
+                    max: 12,
+                    min: 8,
+                    asset_tokens: 10,
+                    crncy_tokens: 92,
+                    time: 0,
+                    counter: 4,
+                }][..]
+            )
+        );
+        assert_eq!(aggregator.candles(0), None);
+        assert_eq!(aggregator.untimed_fills(), 1);
+        assert_eq!(
+            CandleTrade::from_spot_lp_trade(&SpotlpTradeReport::default(), 1),
+            None
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use bytemuck::{Pod, Zeroable};
 
+pub mod fixed_point;
+
 #[cfg(test)]
 mod tests {
     use bytemuck::{Pod, Zeroable};
@@ -21,6 +23,61 @@ mod tests {
 
         assert_eq!(val, VAL as u32);
     }
+
+    #[repr(transparent)]
+    #[drv_macros::new_type(Display, FromStr, Add, Sub, Hash, From, Into)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Zeroable, Pod, Default)]
+    pub struct Signed(pub i64);
+
+    #[test]
+    fn test_forwarded_traits() {
+        let value: Signed = (-5).into();
+
+        assert_eq!(*value.value(), -5i64);
+        assert_eq!(value.to_string(), "-5");
+        assert_eq!("-5".parse::<Signed>(), Ok(value));
+        assert!("five".parse::<Signed>().is_err());
+
+        assert_eq!(value + Signed(7), Signed(2));
+        assert_eq!(value - Signed(7), Signed(-12));
+        assert_eq!(Signed(i64::MAX).checked_add(Signed(1)), None);
+        assert_eq!(Signed(i64::MIN).checked_sub(Signed(1)), None);
+
+        let values = std::collections::HashSet::from([value, Signed(-5), Signed(1)]);
+        assert_eq!(values.len(), 2);
+        assert_eq!(i64::from(value), -5);
+    }
+
+    #[drv_macros::new_type(Display, FromStr, Hash, From, Into)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Label(pub String);
+
+    #[test]
+    fn test_non_copy_inner_type() {
+        let label = Label::from("spot".to_string());
+
+        assert_eq!(label.value(), "spot");
+        assert_eq!(label, "spot".to_string());
+        assert_eq!("spot".parse::<Label>(), Ok(label.clone()));
+        assert_eq!(label.to_string(), "spot");
+        assert_eq!(String::from(label), "spot");
+    }
+
+    #[repr(transparent)]
+    #[drv_macros::new_type(Serialize, Deserialize)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Zeroable, Pod, Default)]
+    pub struct Transparent(pub u32);
+
+    #[test]
+    fn test_forwarded_serde() {
+        let json = serde_json::to_value(Transparent(7)).expect("serialized");
+        assert_eq!(json, serde_json::json!(7));
+        assert_eq!(
+            serde_json::from_value::<Transparent>(json).expect("deserialized"),
+            Transparent(7)
+        );
+        assert!(serde_json::from_value::<Transparent>(serde_json::json!(-1)).is_err());
+    }
 }
 
 pub mod version {
@@ -29,6 +86,7 @@ pub mod version {
     #[repr(transparent)]
     #[drv_macros::new_type]
     #[derive(Debug, Clone, Copy, PartialEq, Zeroable, Pod, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     /// A type-safe wrapper around 'u32' that represents the version of a
     /// contract Ensures that version are handled correctly and prevents
     /// accidental misuse of raw integer values.
@@ -41,6 +99,7 @@ pub mod tag {
     #[repr(transparent)]
     #[drv_macros::new_type]
     #[derive(Debug, Clone, Copy, PartialEq, Zeroable, Pod, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     /// A type-safe wrapper around 'u32' that represents the Tag of an account
     /// Ensures that Tags are handled correctly and prevents
     /// accidental misuse of raw integer values.
@@ -53,6 +112,7 @@ pub mod instrument {
     #[repr(transparent)]
     #[drv_macros::new_type]
     #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Zeroable, Pod, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     /// A type-sfe wrapper around 'u32' that represents an id of an instrument
     /// Ensures that ids are handled correctly and prevents accidental
     /// misuse of raw integer values.
@@ -67,6 +127,7 @@ pub mod client {
     #[repr(transparent)]
     #[drv_macros::new_type]
     #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Zeroable, Pod, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     /// A type-safe wrapper around 'u32' that represents an id of a client
     ///
     /// Ensures that ids are handled correctly and prevents accidental
@@ -79,3 +140,39 @@ pub mod client {
         }
     }
 }
+
+pub mod order {
+    use super::{Pod, Zeroable};
+
+    #[repr(transparent)]
+    #[drv_macros::new_type(Display, FromStr, Hash, Serialize, Deserialize, From, Into)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Zeroable, Pod, Default)]
+    /// A type-safe wrapper around 'i64' that represents an id of an order
+    pub struct OrderId(pub i64);
+}
+
+pub mod token {
+    use super::{Pod, Zeroable};
+
+    #[repr(transparent)]
+    #[drv_macros::new_type(Display, FromStr, Hash, Serialize, Deserialize, From, Into)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Zeroable, Pod, Default)]
+    /// A type-safe wrapper around 'u32' that represents an id of a token
+    pub struct TokenId(pub u32);
+}
+
+pub mod time {
+    use super::{Pod, Zeroable};
+
+    #[repr(transparent)]
+    #[drv_macros::new_type(Display, FromStr, Add, Sub, Hash, Serialize, Deserialize, From, Into)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Zeroable, Pod, Default)]
+    /// A type-safe wrapper around 'u32' that represents a Solana slot
+    pub struct Slot(pub u32);
+
+    #[repr(transparent)]
+    #[drv_macros::new_type(Display, FromStr, Add, Sub, Hash, Serialize, Deserialize, From, Into)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Zeroable, Pod, Default)]
+    /// A type-safe wrapper around 'u32' that represents unix time in seconds
+    pub struct UnixTime(pub u32);
+}
@@ -0,0 +1,366 @@
+//! # Fixed point module
+//! - `Price` is scaled by `DF` (`PRICE_DECIMALS` decimals)
+//! - `Qty` is scaled by asset token decimals, `CrncyAmount` by crncy token decimals
+//! - Instrument `dec_factor` links them: `sum = price * qty / dec_factor`
+//! - Intermediate products use `i128`, results not fitting `i64` are
+//!   `ArithmeticOverflow`
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::errors::ModelsError;
+
+/// Decimals of `Price`, `10^PRICE_DECIMALS == DF`
+pub const PRICE_DECIMALS: u32 = 9;
+
+/// Rounding of results not representable with the target decimals
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    TowardZero,
+    AwayFromZero,
+    Floor,
+    Ceil,
+    /// Half away from zero
+    Nearest,
+}
+
+/// Divides `num` by `den` with `rounding`
+///
+/// ### Errors
+/// - `DivisionByZero` - `den` is zero
+/// - `ArithmeticOverflow` - result does not fit `i64`
+pub fn div_round(num: i128, den: i128, rounding: Rounding) -> Result<i64, ModelsError> {
+    if den == 0 {
+        return Err(ModelsError::DivisionByZero);
+    }
+
+    let (num, den) = if den < 0 {
+        (num.checked_neg(), den.checked_neg())
+    } else {
+        (Some(num), Some(den))
+    };
+    let (Some(num), Some(den)) = (num, den) else {
+        return Err(ModelsError::ArithmeticOverflow);
+    };
+
+    let quotient = num / den;
+    let remainder = num % den;
+    let sign = num.signum();
+
+    let result = if remainder == 0 {
+        quotient
+    } else {
+        match rounding {
+            Rounding::AwayFromZero => quotient + sign,
+            Rounding::Floor if num < 0 => quotient - 1,
+            Rounding::Ceil if num > 0 => quotient + 1,
+            Rounding::Nearest if remainder.unsigned_abs() * 2 >= den.unsigned_abs() => {
+                quotient + sign
+            }
+            Rounding::TowardZero | Rounding::Floor | Rounding::Ceil | Rounding::Nearest => quotient,
+        }
+    };
+
+    i64::try_from(result).map_err(|_| ModelsError::ArithmeticOverflow)
+}
+
+/// Parses a decimal string like `-12.345` into a value with `decimals` decimals
+///
+/// Fraction digits beyond `decimals` are rounded with `rounding`, however
+/// many of them are given
+///
+/// ### Errors
+/// - `InvalidClientDataFormat` - string is not a plain decimal number
+/// - `ArithmeticOverflow` - value does not fit `i64`
+pub fn parse_decimal(value: &str, decimals: u32, rounding: Rounding) -> Result<i64, ModelsError> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+
+    if (int_part.is_empty() && frac_part.is_empty())
+        || !int_part
+            .bytes()
+            .chain(frac_part.bytes())
+            .all(|byte| byte.is_ascii_digit())
+    {
+        return Err(ModelsError::InvalidClientDataFormat);
+    }
+
+    let (frac_kept, frac_dropped) = frac_part.split_at(frac_part.len().min(decimals as usize));
+    let scale = 10i128
+        .checked_pow(decimals - frac_kept.len() as u32)
+        .ok_or(ModelsError::ArithmeticOverflow)?;
+
+    let magnitude = int_part
+        .bytes()
+        .chain(frac_kept.bytes())
+        .try_fold(0i128, |magnitude, byte| {
+            magnitude
+                .checked_mul(10)?
+                .checked_add(i128::from(byte - b'0'))
+        })
+        .and_then(|magnitude| magnitude.checked_mul(scale))
+        .ok_or(ModelsError::ArithmeticOverflow)?;
+
+    let inexact = frac_dropped.bytes().any(|byte| byte != b'0');
+    let away_from_zero = match rounding {
+        Rounding::TowardZero => false,
+        Rounding::AwayFromZero => inexact,
+        Rounding::Floor => negative && inexact,
+        Rounding::Ceil => !negative && inexact,
+        Rounding::Nearest => frac_dropped.bytes().next().is_some_and(|byte| byte >= b'5'),
+    };
+    let magnitude = magnitude + i128::from(away_from_zero);
+
+    i64::try_from(if negative { -magnitude } else { magnitude })
+        .map_err(|_| ModelsError::ArithmeticOverflow)
+}
+
+/// Formats a value with `decimals` decimals, trailing zeros are trimmed
+pub fn format_decimal(value: i64, decimals: u32) -> String {
+    let digits = format!(
+        "{:0width$}",
+        value.unsigned_abs(),
+        width = decimals as usize + 1
+    );
+    let (int_part, frac_part) = digits.split_at(digits.len() - decimals as usize);
+    let frac_part = frac_part.trim_end_matches('0');
+    let sign = if value < 0 { "-" } else { "" };
+
+    if frac_part.is_empty() {
+        format!("{}{}", sign, int_part)
+    } else {
+        format!("{}{}.{}", sign, int_part, frac_part)
+    }
+}
+
+macro_rules! fixed_point_impl {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl $name {
+                pub fn checked_add(self, other: Self) -> Result<Self, ModelsError> {
+                    self.0
+                        .checked_add(other.0)
+                        .map(Self)
+                        .ok_or(ModelsError::ArithmeticOverflow)
+                }
+
+                pub fn checked_sub(self, other: Self) -> Result<Self, ModelsError> {
+                    self.0
+                        .checked_sub(other.0)
+                        .map(Self)
+                        .ok_or(ModelsError::ArithmeticOverflow)
+                }
+
+                /// `self * mul / div` with `rounding`
+                pub fn checked_mul_div(
+                    self,
+                    mul: i64,
+                    div: i64,
+                    rounding: Rounding,
+                ) -> Result<Self, ModelsError> {
+                    div_round(i128::from(self.0) * i128::from(mul), i128::from(div), rounding)
+                        .map(Self)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! token_amount_impl {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl $name {
+                /// Parses a decimal string given token decimals
+                pub fn from_decimal_str(
+                    value: &str,
+                    decs_count: u32,
+                    rounding: Rounding,
+                ) -> Result<Self, ModelsError> {
+                    parse_decimal(value, decs_count, rounding).map(Self)
+                }
+
+                /// Formats as a decimal string given token decimals
+                pub fn to_decimal_string(self, decs_count: u32) -> String {
+                    format_decimal(self.0, decs_count)
+                }
+            }
+        )*
+    };
+}
+
+#[repr(transparent)]
+#[drv_macros::new_type]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Zeroable, Pod, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// A type-safe wrapper around 'i64' that represents a price scaled by `DF`
+pub struct Price(pub i64);
+
+#[repr(transparent)]
+#[drv_macros::new_type]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Zeroable, Pod, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// A type-safe wrapper around 'i64' that represents an asset quantity in
+/// asset token units
+pub struct Qty(pub i64);
+
+#[repr(transparent)]
+#[drv_macros::new_type]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Zeroable, Pod, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// A type-safe wrapper around 'i64' that represents a crncy amount in
+/// crncy token units
+pub struct CrncyAmount(pub i64);
+
+fixed_point_impl!(Price, Qty, CrncyAmount);
+token_amount_impl!(Qty, CrncyAmount);
+
+impl Price {
+    pub fn from_decimal_str(value: &str, rounding: Rounding) -> Result<Self, ModelsError> {
+        parse_decimal(value, PRICE_DECIMALS, rounding).map(Self)
+    }
+
+    pub fn to_decimal_string(self) -> String {
+        format_decimal(self.0, PRICE_DECIMALS)
+    }
+
+    /// Order sum `price * qty / dec_factor`
+    pub fn sum(
+        self,
+        qty: Qty,
+        dec_factor: i64,
+        rounding: Rounding,
+    ) -> Result<CrncyAmount, ModelsError> {
+        div_round(
+            i128::from(self.0) * i128::from(qty.0),
+            i128::from(dec_factor),
+            rounding,
+        )
+        .map(CrncyAmount)
+    }
+}
+
+impl Qty {
+    /// Quantity bought for `sum` at `price`, `sum * dec_factor / price`
+    pub fn from_sum(
+        sum: CrncyAmount,
+        price: Price,
+        dec_factor: i64,
+        rounding: Rounding,
+    ) -> Result<Self, ModelsError> {
+        div_round(
+            i128::from(sum.0) * i128::from(dec_factor),
+            i128::from(price.0),
+            rounding,
+        )
+        .map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_conversion() {
+        assert_eq!(
+            Price::from_decimal_str("1.5", Rounding::Nearest),
+            Ok(Price(1_500_000_000))
+        );
+        assert_eq!(Price(-1_500_000_000).to_decimal_string(), "-1.5");
+        assert_eq!(Qty(2_000_000).to_decimal_string(6), "2");
+        assert_eq!(Qty(5).to_decimal_string(6), "0.000005");
+        assert_eq!(
+            CrncyAmount::from_decimal_str(".25", 2, Rounding::Nearest),
+            Ok(CrncyAmount(25))
+        );
+
+        assert_eq!(
+            Qty::from_decimal_str("1.2345", 3, Rounding::TowardZero),
+            Ok(Qty(1_234))
+        );
+        assert_eq!(
+            Qty::from_decimal_str("1.2345", 3, Rounding::Nearest),
+            Ok(Qty(1_235))
+        );
+        assert_eq!(
+            Qty::from_decimal_str("-1.2345", 3, Rounding::Floor),
+            Ok(Qty(-1_235))
+        );
+        assert_eq!(
+            Qty::from_decimal_str("-1.2345", 3, Rounding::Ceil),
+            Ok(Qty(-1_234))
+        );
+
+        for invalid in ["", "-", ".", "1e5", "+1", "1.2.3", " 1"] {
+            assert_eq!(
+                Qty::from_decimal_str(invalid, 3, Rounding::Nearest),
+                Err(ModelsError::InvalidClientDataFormat)
+            );
+        }
+        assert_eq!(
+            Price::from_decimal_str("10000000000", Rounding::Nearest),
+            Err(ModelsError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn test_long_fraction() {
+        let digits = "1.".to_string() + &"9".repeat(60);
+
+        assert_eq!(
+            Qty::from_decimal_str(&digits, 2, Rounding::TowardZero),
+            Ok(Qty(199))
+        );
+        assert_eq!(
+            Qty::from_decimal_str(&digits, 2, Rounding::Nearest),
+            Ok(Qty(200))
+        );
+        assert_eq!(
+            Qty::from_decimal_str(&format!("-{}", digits), 2, Rounding::Floor),
+            Ok(Qty(-200))
+        );
+        assert_eq!(
+            Qty::from_decimal_str(&("0.5".to_string() + &"0".repeat(60)), 0, Rounding::Ceil),
+            Ok(Qty(1))
+        );
+        assert_eq!(
+            Qty::from_decimal_str(&("0.4".to_string() + &"9".repeat(60)), 0, Rounding::Nearest),
+            Ok(Qty(0))
+        );
+        assert_eq!(
+            Qty::from_decimal_str(&("2.".to_string() + &"0".repeat(60)), 0, Rounding::Ceil),
+            Ok(Qty(2))
+        );
+    }
+
+    #[test]
+    fn test_sum() {
+        // 1 asset with 6 decimals for 2.5 crncy with 2 decimals
+        let dec_factor = 10i64.pow(PRICE_DECIMALS + 6 - 2);
+        let price = Price(2_500_000_000);
+        let qty = Qty(1_000_000);
+
+        assert_eq!(
+            price.sum(qty, dec_factor, Rounding::Nearest),
+            Ok(CrncyAmount(250))
+        );
+        assert_eq!(
+            Qty::from_sum(CrncyAmount(250), price, dec_factor, Rounding::Nearest),
+            Ok(qty)
+        );
+        assert_eq!(
+            price.sum(Qty(1), dec_factor, Rounding::Ceil),
+            Ok(CrncyAmount(1))
+        );
+        assert_eq!(
+            price.sum(qty, 0, Rounding::Nearest),
+            Err(ModelsError::DivisionByZero)
+        );
+        assert_eq!(
+            Qty(i64::MAX).checked_add(Qty(1)),
+            Err(ModelsError::ArithmeticOverflow)
+        );
+    }
+}
@@ -1,9 +1,15 @@
-use super::types::Discriminator;
-use crate::new_types::instrument::InstrId;
+use super::{
+    records::records,
+    types::{account_type::AccountType, Discriminator},
+};
+use crate::{
+    constants::candles::CandleParams, errors::ModelsError, new_types::instrument::InstrId,
+};
 use bytemuck::{Pod, Zeroable};
 use drv_macros::pod_wrapper;
+use solana_pubkey::Pubkey;
 
-use std::mem::size_of;
+use std::{mem::size_of, num::NonZeroU32};
 
 /// Represents a single price candle in a circular buffer of candles (`CandleBuffer`).
 ///
@@ -24,7 +30,7 @@ use std::mem::size_of;
 /// - The `time` field is always aligned to the candle duration.
 /// - Allowed price limits `MIN_PRICE..MAX_PRICE`
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
 pub struct Candle {
     pub open: i64,
     pub close: i64,
@@ -38,7 +44,7 @@ pub struct Candle {
 pub const CANDLE_SIZE: usize = size_of::<Candle>();
 
 #[derive(Debug, PartialEq)]
-#[pod_wrapper]
+#[pod_wrapper(size = 24, align = 4)]
 #[repr(C)]
 #[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
 /// Describe the state of candles buffer based on candles tag and instrument.
@@ -54,3 +60,322 @@ pub struct CandlesAccountHeader<const TAG: u32> {
 }
 
 pub const CANDLES_ACCOUNT_HEADER_SIZE: usize = size_of::<CandlesAccountHeader<0>>();
+
+impl Candle {
+    /// Candle without trades keeping `price`
+    pub const fn flat(price: i64, time: u32) -> Self {
+        Self {
+            open: price,
+            close: price,
+            max: price,
+            min: price,
+            asset_tokens: 0,
+            crncy_tokens: 0,
+            time,
+            counter: 0,
+        }
+    }
+
+    /// Extends the candle with a later candle of the same period
+    pub fn merge(&mut self, next: &Self) {
+        self.close = next.close;
+        self.max = self.max.max(next.max);
+        self.min = self.min.min(next.min);
+        self.asset_tokens += next.asset_tokens;
+        self.crncy_tokens += next.crncy_tokens;
+        self.counter += next.counter;
+    }
+}
+
+/// Inserts flat candles for periods without trades between chronological candles
+pub fn fill_gaps(candles: impl IntoIterator<Item = Candle>, duration: NonZeroU32) -> Vec<Candle> {
+    let duration = duration.get();
+    let mut filled: Vec<Candle> = Vec::new();
+
+    for candle in candles {
+        if let Some(prev) = filled.last().copied() {
+            let mut time = prev.time.saturating_add(duration);
+
+            while time < candle.time {
+                filled.push(Candle::flat(prev.close, time));
+                time = time.saturating_add(duration);
+            }
+        }
+        filled.push(candle);
+    }
+
+    filled
+}
+
+/// Merges chronological candles into candles of `duration`
+///
+/// `duration` is expected to be a multiple of the source candles duration,
+/// resampled candles are aligned with `time % duration == 0`
+pub fn resample(candles: impl IntoIterator<Item = Candle>, duration: NonZeroU32) -> Vec<Candle> {
+    let mut resampled: Vec<Candle> = Vec::new();
+
+    for candle in candles {
+        let time = candle.time - candle.time % duration;
+
+        match resampled.last_mut() {
+            Some(last) if last.time == time => last.merge(&candle),
+            _ => resampled.push(Candle { time, ..candle }),
+        }
+    }
+
+    resampled
+}
+
+/// Candle Buffer
+///
+/// Read only view over the ring of candles following `CandlesAccountHeader`.
+/// The ring holds `count` allocated candles and `last` is the most recent one,
+/// so the oldest candle is stored right after `last`.
+#[derive(Clone, Copy, Debug)]
+pub struct CandleBuffer<'a> {
+    candles: &'a [Candle],
+    last: u32,
+    duration: NonZeroU32,
+}
+
+impl<'a> CandleBuffer<'a> {
+    /// Allocated candles of the ring with `last` index
+    ///
+    /// ### Errors
+    /// - `OffsetOutOfBounds` - `last` is outside of allocated candles
+    pub const fn new(
+        candles: &'a [Candle],
+        last: u32,
+        duration: NonZeroU32,
+        address: &Pubkey,
+    ) -> Result<Self, ModelsError> {
+        if !candles.is_empty() && last as usize >= candles.len() {
+            return Err(ModelsError::OffsetOutOfBounds {
+                address: *address,
+                offset: CANDLES_ACCOUNT_HEADER_SIZE + last as usize * CANDLE_SIZE,
+                len: CANDLES_ACCOUNT_HEADER_SIZE + candles.len() * CANDLE_SIZE,
+            });
+        }
+
+        Ok(Self {
+            candles,
+            last,
+            duration,
+        })
+    }
+
+    /// Reads candles account data described by `params`
+    ///
+    /// ### Errors
+    /// - `InvalidClientDataFormat` - unknown `params.tag` or zero `params.duration`
+    /// - `InvalidAccountSize` - data is smaller than the header or `count` candles
+    /// - `InvalidAccountTag` - account does not store `params.tag` candles
+    /// - `InvalidDataAlignment` - account data is not aligned
+    /// - `OffsetOutOfBounds` - `last` is outside of allocated candles
+    pub fn load(
+        data: &'a [u8],
+        address: &Pubkey,
+        params: &CandleParams,
+    ) -> Result<Self, ModelsError> {
+        let account_type =
+            AccountType::from_tag(params.tag).ok_or(ModelsError::InvalidClientDataFormat)?;
+        let duration =
+            NonZeroU32::new(params.duration).ok_or(ModelsError::InvalidClientDataFormat)?;
+        let invalid_size = |expected: usize| ModelsError::InvalidAccountSize {
+            address: *address,
+            account_type,
+            expected,
+            actual: data.len(),
+        };
+
+        let header = data
+            .get(..CANDLES_ACCOUNT_HEADER_SIZE)
+            .and_then(|data| bytemuck::try_pod_read_unaligned::<CandlesAccountHeader<0>>(data).ok())
+            .ok_or_else(|| invalid_size(CANDLES_ACCOUNT_HEADER_SIZE))?;
+
+        if *header.discriminator.tag != params.tag {
+            return Err(ModelsError::InvalidAccountTag {
+                expected_account_type: account_type,
+                actual_tag: *header.discriminator.tag,
+                address: *address,
+            });
+        }
+
+        let count = header.count.min(params.capacity) as usize;
+        let candles = records::<Candle>(data, CANDLES_ACCOUNT_HEADER_SIZE, address)?;
+
+        Self::new(
+            candles
+                .get(..count)
+                .ok_or_else(|| invalid_size(CANDLES_ACCOUNT_HEADER_SIZE + count * CANDLE_SIZE))?,
+            header.last,
+            duration,
+            address,
+        )
+    }
+
+    pub const fn len(&self) -> usize {
+        self.candles.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.candles.is_empty()
+    }
+
+    pub const fn duration(&self) -> u32 {
+        self.duration.get()
+    }
+
+    /// Most recent candle
+    pub fn last(&self) -> Option<&'a Candle> {
+        self.candles.get(self.last as usize)
+    }
+
+    /// Iterates over candles from the oldest to the most recent one
+    pub fn iter(&self) -> impl Iterator<Item = &'a Candle> + 'a {
+        let candles = self.candles;
+        let oldest = self.last as usize + 1;
+
+        (0..candles.len()).map(move |index| &candles[(oldest + index) % candles.len()])
+    }
+
+    /// Chronological candles with flat candles for periods without trades
+    pub fn filled(&self) -> Vec<Candle> {
+        fill_gaps(self.iter().copied(), self.duration)
+    }
+
+    /// Chronological candles of `duration`, periods without trades are filled
+    pub fn resample(&self, duration: NonZeroU32) -> Vec<Candle> {
+        fill_gaps(resample(self.iter().copied(), duration), duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::{candles::CANDLES, time::HOUR},
+        new_types::{tag::Tag, version::Version},
+        state::{records::test_account, types::account_type::SPOT_1M_CANDLES},
+    };
+
+    fn candle(price: i64, time: u32) -> Candle {
+        Candle {
+            asset_tokens: 1,
+            crncy_tokens: price,
+            counter: 1,
+            ..Candle::flat(price, time)
+        }
+    }
+
+    fn account(candles: &[Candle], last: u32) -> Vec<u64> {
+        let header = CandlesAccountHeader::<SPOT_1M_CANDLES>::new(
+            Discriminator::new(Tag(SPOT_1M_CANDLES), Version(1)),
+            InstrId(1),
+            0,
+            candles.len() as u32,
+            last,
+        );
+        test_account(bytemuck::bytes_of(&header), candles)
+    }
+
+    #[test]
+    fn test_candle_buffer() {
+        // Ring wrapped after the candle at 180, the oldest candle is at 0
+        let data = account(&[candle(4, 180), candle(1, 0), candle(2, 60)], 0);
+        let buffer = match CandleBuffer::load(
+            bytemuck::cast_slice(&data),
+            &Pubkey::new_unique(),
+            &CANDLES[0],
+        ) {
+            Ok(buffer) => buffer,
+            Err(err) => panic!("Failed to load candles: {}", err),
+        };
+
+        assert_eq!(buffer.last().map(|candle| candle.time), Some(180));
+        assert_eq!(
+            buffer.iter().map(|candle| candle.time).collect::<Vec<_>>(),
+            vec![0, 60, 180]
+        );
+        assert_eq!(buffer.filled()[2], Candle::flat(2, 120));
+
+        let resampled = buffer.resample(NonZeroU32::new(HOUR).expect("Hour is not zero"));
+        assert_eq!(
+            resampled,
+            vec![Candle {
+                open: 1,
+                close: 4,
+                max: 4,
+                min: 1,
+                asset_tokens: 3,
+                crncy_tokens: 7,
+                time: 0,
+                counter: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_candle_buffer_errors() {
+        let address = Pubkey::new_unique();
+        let data = account(&[candle(1, 0)], 1);
+
+        assert_eq!(
+            CandleBuffer::load(bytemuck::cast_slice(&data), &address, &CANDLES[0]).err(),
+            Some(ModelsError::OffsetOutOfBounds {
+                address,
+                offset: CANDLES_ACCOUNT_HEADER_SIZE + CANDLE_SIZE,
+                len: CANDLES_ACCOUNT_HEADER_SIZE + CANDLE_SIZE,
+            })
+        );
+        assert!(matches!(
+            CandleBuffer::load(bytemuck::cast_slice(&data), &address, &CANDLES[1]),
+            Err(ModelsError::InvalidAccountTag { .. })
+        ));
+        assert_eq!(
+            CandleBuffer::load(
+                bytemuck::cast_slice(&data),
+                &address,
+                &CandleParams {
+                    duration: 0,
+                    ..CANDLES[0]
+                }
+            )
+            .err(),
+            Some(ModelsError::InvalidClientDataFormat)
+        );
+    }
+
+    #[test]
+    fn test_resample_gaps() {
+        let candles = [candle(1, 0), candle(2, 900), candle(3, 3 * 900)];
+        let duration = |duration| NonZeroU32::new(duration).expect("Duration is not zero");
+
+        assert_eq!(
+            resample(candles, duration(1_800))
+                .iter()
+                .map(|candle| (candle.time, candle.open, candle.close))
+                .collect::<Vec<_>>(),
+            vec![(0, 1, 2), (1_800, 3, 3)]
+        );
+        assert_eq!(fill_gaps(candles, duration(900)).len(), 4);
+    }
+
+    #[test]
+    fn test_header_field_offsets() {
+        assert_eq!(
+            CandlesAccountHeader::<SPOT_1M_CANDLES>::FIELD_OFFSETS,
+            &[
+                ("discriminator", 0),
+                ("id", 8),
+                ("slot", 12),
+                ("count", 16),
+                ("last", 20),
+            ]
+        );
+        assert_eq!(
+            CandlesAccountHeader::<SPOT_1M_CANDLES>::FIELD_OFFSETS,
+            CandlesAccountHeaderNonGen::FIELD_OFFSETS
+        );
+    }
+}
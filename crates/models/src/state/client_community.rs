@@ -33,6 +33,7 @@ pub struct ClientCommunityRecord {
 
 pub const CLIENT_COMMUNITY_RECORD_SIZE: usize = size_of::<ClientCommunityRecord>();
 
+#[drv_macros::drv_layout(size = 64, align = 8)]
 #[repr(C)]
 #[derive(Pod, Zeroable, Clone, Copy)]
 /// Clients community account header
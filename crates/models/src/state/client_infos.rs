@@ -0,0 +1,220 @@
+//! # Client infos module
+//! - Checked views over client info accounts
+//! - Records follow the account header and are indexed by temp client id
+//! - Accounts grow with every allocated client, so each complete record
+//!   stored in data is an occupied slot
+
+use bytemuck::Pod;
+use solana_pubkey::Pubkey;
+
+use std::{marker::PhantomData, mem::size_of};
+
+use crate::{
+    errors::ModelsError,
+    new_types::client::ClientId,
+    state::records::{cast_error, records},
+};
+
+fn record_range<T>(
+    header_size: usize,
+    len: usize,
+    id: ClientId,
+    address: &Pubkey,
+) -> Result<std::ops::Range<usize>, ModelsError> {
+    let offset = (*id as usize)
+        .checked_mul(size_of::<T>())
+        .and_then(|offset| offset.checked_add(header_size));
+
+    match offset.and_then(|offset| Some(offset..offset.checked_add(size_of::<T>())?)) {
+        Some(range) if range.end <= len => Ok(range),
+        _ => Err(ModelsError::OffsetOutOfBounds {
+            address: *address,
+            offset: offset.unwrap_or(usize::MAX),
+            len,
+        }),
+    }
+}
+
+/// Read only view over `T` records stored after a `HEADER_SIZE` header
+pub struct ClientInfos<'a, T, const HEADER_SIZE: usize> {
+    data: &'a [u8],
+    address: Pubkey,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: Pod, const HEADER_SIZE: usize> ClientInfos<'a, T, HEADER_SIZE> {
+    pub const fn new(data: &'a [u8], address: Pubkey) -> Self {
+        Self {
+            data,
+            address,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Amount of records stored in data
+    pub const fn len(&self) -> usize {
+        self.data.len().saturating_sub(HEADER_SIZE) / size_of::<T>()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// ### Errors
+    /// - `OffsetOutOfBounds` - record is not fully stored in data
+    /// - `InvalidDataAlignment` - record is not aligned
+    pub fn get(&self, id: ClientId) -> Result<&'a T, ModelsError> {
+        let range = record_range::<T>(HEADER_SIZE, self.data.len(), id, &self.address)?;
+
+        bytemuck::try_from_bytes(&self.data[range]).map_err(|err| cast_error(err, &self.address))
+    }
+
+    /// All occupied slots indexed by temp client id
+    pub fn as_slice(&self) -> Result<&'a [T], ModelsError> {
+        records(self.data, HEADER_SIZE, &self.address)
+    }
+
+    /// Iterates over occupied slots
+    pub fn iter(&self) -> Result<impl Iterator<Item = (ClientId, &'a T)>, ModelsError> {
+        Ok(self
+            .as_slice()?
+            .iter()
+            .enumerate()
+            .map(|(id, info)| (ClientId(id as u32), info)))
+    }
+}
+
+/// Mutable view over `T` records stored after a `HEADER_SIZE` header
+pub struct ClientInfosMut<'a, T, const HEADER_SIZE: usize> {
+    data: &'a mut [u8],
+    address: Pubkey,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: Pod, const HEADER_SIZE: usize> ClientInfosMut<'a, T, HEADER_SIZE> {
+    pub const fn new(data: &'a mut [u8], address: Pubkey) -> Self {
+        Self {
+            data,
+            address,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Amount of records stored in data
+    pub const fn len(&self) -> usize {
+        self.data.len().saturating_sub(HEADER_SIZE) / size_of::<T>()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// ### Errors
+    /// - `OffsetOutOfBounds` - record is not fully stored in data
+    /// - `InvalidDataAlignment` - record is not aligned
+    pub fn get(&self, id: ClientId) -> Result<&T, ModelsError> {
+        let range = record_range::<T>(HEADER_SIZE, self.data.len(), id, &self.address)?;
+
+        bytemuck::try_from_bytes(&self.data[range]).map_err(|err| cast_error(err, &self.address))
+    }
+
+    /// ### Errors
+    /// - `OffsetOutOfBounds` - record is not fully stored in data
+    /// - `InvalidDataAlignment` - record is not aligned
+    pub fn get_mut(&mut self, id: ClientId) -> Result<&mut T, ModelsError> {
+        let range = record_range::<T>(HEADER_SIZE, self.data.len(), id, &self.address)?;
+
+        bytemuck::try_from_bytes_mut(&mut self.data[range])
+            .map_err(|err| cast_error(err, &self.address))
+    }
+
+    /// All occupied slots indexed by temp client id
+    pub fn as_mut_slice(&mut self) -> Result<&mut [T], ModelsError> {
+        let end = HEADER_SIZE + self.len() * size_of::<T>();
+
+        bytemuck::try_cast_slice_mut(self.data.get_mut(HEADER_SIZE..end).unwrap_or_default())
+            .map_err(|err| cast_error(err, &self.address))
+    }
+
+    /// Iterates over occupied slots
+    pub fn iter_mut(&mut self) -> Result<impl Iterator<Item = (ClientId, &mut T)>, ModelsError> {
+        Ok(self
+            .as_mut_slice()?
+            .iter_mut()
+            .enumerate()
+            .map(|(id, info)| (ClientId(id as u32), info)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::perps::perp_infos::{PerpClientInfo, PerpClientInfos, PerpClientInfosMut};
+    use crate::state::perps::perp_trade_header::PERP_TRADE_ACCOUNT_HEADER_SIZE;
+    use crate::state::records::test_account;
+    use bytemuck::Zeroable;
+
+    fn account(records: usize) -> Vec<u64> {
+        test_account(
+            &[0; PERP_TRADE_ACCOUNT_HEADER_SIZE],
+            &vec![PerpClientInfo::zeroed(); records],
+        )
+    }
+
+    #[test]
+    fn test_client_infos() {
+        let address = Pubkey::new_unique();
+        let mut data = account(2);
+
+        let mut infos =
+            PerpClientInfosMut::<PerpClientInfo>::new(bytemuck::cast_slice_mut(&mut data), address);
+        assert_eq!(infos.len(), 2);
+        infos
+            .get_mut(ClientId(1))
+            .expect("Record 1 is stored")
+            .funds = 100;
+        infos
+            .iter_mut()
+            .expect("Records are aligned")
+            .for_each(|(id, info)| info.perps = *id as i64 + 1);
+
+        let infos = PerpClientInfos::<PerpClientInfo>::new(bytemuck::cast_slice(&data), address);
+        assert_eq!(infos.get(ClientId(1)).map(|info| info.funds), Ok(100));
+        assert_eq!(
+            infos
+                .iter()
+                .map(|iter| iter.map(|(_, info)| info.perps).collect::<Vec<_>>()),
+            Ok(vec![1, 2])
+        );
+    }
+
+    #[test]
+    fn test_client_infos_out_of_bounds() {
+        let address = Pubkey::new_unique();
+        let data = account(2);
+        let bytes: &[u8] = bytemuck::cast_slice(&data);
+
+        let infos = PerpClientInfos::<PerpClientInfo>::new(bytes, address);
+        assert_eq!(
+            infos.get(ClientId(2)).err(),
+            Some(ModelsError::OffsetOutOfBounds {
+                address,
+                offset: PERP_TRADE_ACCOUNT_HEADER_SIZE + 2 * size_of::<PerpClientInfo>(),
+                len: bytes.len(),
+            })
+        );
+        assert_eq!(
+            infos
+                .get(ClientId(u32::MAX))
+                .err()
+                .map(|err| matches!(err, ModelsError::OffsetOutOfBounds { .. })),
+            Some(true)
+        );
+
+        let infos = PerpClientInfos::<PerpClientInfo>::new(&bytes[4..], address);
+        assert_eq!(
+            infos.get(ClientId(0)).err(),
+            Some(ModelsError::InvalidDataAlignment { address })
+        );
+    }
+}
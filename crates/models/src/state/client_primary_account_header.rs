@@ -39,6 +39,7 @@ use solana_pubkey::Pubkey;
 /// 25. **`points`** - Amount of points for Points Program
 /// 26. **`slot`** - Record last **writable** manipulation with ClientPrimaryAccountHeader
 /// 27. **`assets_count`** - Length of assets record array
+#[drv_macros::drv_layout(size = 376, align = 8)]
 #[repr(C)]
 #[derive(Pod, Zeroable, Clone, Copy)]
 pub struct ClientPrimaryAccountHeader {
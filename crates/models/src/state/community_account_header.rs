@@ -25,6 +25,7 @@ use super::types::Discriminator;
 /// 19. **`max_discount`** - Raw record. Represent a maximum discount in fees prepayment program.
 /// 20. **`count`** - Length of `BaseCrncyRecord` array
 
+#[drv_macros::drv_layout(size = 160, align = 8)]
 #[repr(C)]
 #[derive(Pod, Zeroable, Clone, Copy)]
 pub struct CommunityAccountHeader {
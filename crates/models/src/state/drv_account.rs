@@ -0,0 +1,224 @@
+use bytemuck::Pod;
+use solana_pubkey::Pubkey;
+
+use std::mem::{align_of, size_of};
+
+use super::{
+    candles::CandlesAccountHeader,
+    client_community::ClientCommunityAccountHeader,
+    client_primary_account_header::ClientPrimaryAccountHeader,
+    community_account_header::CommunityAccountHeader,
+    instrument::InstrAccountHeader,
+    perps::perp_trade_header::PerpTradeAccountHeader,
+    private_mode::PrivateClientHeader,
+    root::RootState,
+    spots::spot_account_header::SpotTradeAccountHeader,
+    token::TokenState,
+    types::{account_type::AccountType, Discriminator},
+};
+use crate::{errors::ModelsError, new_types::version::Version};
+
+/// Deriverse Account
+///
+/// Typed zero-copy view over account data starting with a `Discriminator`.
+/// `load` and `load_mut` check in one place that data:
+///
+/// 1. Is large enough to hold `Self` (`InvalidAccountSize`)
+/// 2. Is aligned for `Self` (`InvalidDataAlignment`)
+/// 3. Stores `TAG` in the discriminator (`InvalidAccountTag`)
+/// 4. Stores the expected version (`InvalidVersion`)
+///
+/// Only the header prefix is borrowed, trailing records are left to the caller.
+pub trait DrvAccount: Pod {
+    const ACCOUNT_TYPE: AccountType;
+    const TAG: u32 = Self::ACCOUNT_TYPE as u32;
+
+    fn load<'a>(
+        data: &'a [u8],
+        address: &Pubkey,
+        version: Version,
+    ) -> Result<&'a Self, ModelsError> {
+        check::<Self>(data, address, version)?;
+
+        Ok(bytemuck::from_bytes(&data[..size_of::<Self>()]))
+    }
+
+    fn load_mut<'a>(
+        data: &'a mut [u8],
+        address: &Pubkey,
+        version: Version,
+    ) -> Result<&'a mut Self, ModelsError> {
+        check::<Self>(data, address, version)?;
+
+        Ok(bytemuck::from_bytes_mut(&mut data[..size_of::<Self>()]))
+    }
+}
+
+fn check<T: DrvAccount>(
+    data: &[u8],
+    address: &Pubkey,
+    version: Version,
+) -> Result<(), ModelsError> {
+    if data.len() < size_of::<T>() {
+        return Err(ModelsError::InvalidAccountSize {
+            address: *address,
+            account_type: T::ACCOUNT_TYPE,
+            expected: size_of::<T>(),
+            actual: data.len(),
+        });
+    }
+
+    if data.as_ptr().align_offset(align_of::<T>()) != 0 {
+        return Err(ModelsError::InvalidDataAlignment { address: *address });
+    }
+
+    // Discriminator is the first field of every account
+    let discriminator: &Discriminator = bytemuck::from_bytes(&data[..size_of::<Discriminator>()]);
+
+    if *discriminator.tag != T::TAG {
+        return Err(ModelsError::InvalidAccountTag {
+            expected_account_type: T::ACCOUNT_TYPE,
+            actual_tag: *discriminator.tag,
+            address: *address,
+        });
+    }
+
+    if discriminator.version != version {
+        return Err(ModelsError::InvalidVersion {
+            address: *address,
+            account_type: T::ACCOUNT_TYPE,
+            expected: *version,
+            actual: *discriminator.version,
+        });
+    }
+
+    Ok(())
+}
+
+/// Account type of generic headers, unknown tags fail at compile time
+const fn account_type(tag: u32) -> AccountType {
+    match AccountType::from_tag(tag) {
+        Some(account_type) => account_type,
+        None => panic!("Unknown account tag"),
+    }
+}
+
+macro_rules! drv_accounts {
+    ($($account:ty => $account_type:ident),* $(,)?) => {
+        $(
+            impl DrvAccount for $account {
+                const ACCOUNT_TYPE: AccountType = AccountType::$account_type;
+            }
+        )*
+    };
+}
+
+drv_accounts! {
+    RootState => Root,
+    TokenState => Token,
+    InstrAccountHeader => Instr,
+    ClientPrimaryAccountHeader => ClientPrimary,
+    CommunityAccountHeader => Community,
+    ClientCommunityAccountHeader => ClientCommunity,
+    PrivateClientHeader => PrivateClients,
+}
+
+impl<const TAG: u32> DrvAccount for CandlesAccountHeader<TAG> {
+    const ACCOUNT_TYPE: AccountType = account_type(TAG);
+}
+
+impl<const TAG: u32> DrvAccount for SpotTradeAccountHeader<TAG> {
+    const ACCOUNT_TYPE: AccountType = account_type(TAG);
+}
+
+impl<const TAG: u32> DrvAccount for PerpTradeAccountHeader<TAG> {
+    const ACCOUNT_TYPE: AccountType = account_type(TAG);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{new_types::tag::Tag, state::types::account_type};
+    use bytemuck::Zeroable;
+
+    #[test]
+    fn test_load() {
+        let address = Pubkey::new_unique();
+        let root = RootState {
+            discriminator: Discriminator::new(Tag(account_type::ROOT), Version(3)),
+            clients_count: 7,
+            ..Default::default()
+        };
+
+        let mut data = bytemuck::bytes_of(&root).to_vec();
+        data.extend_from_slice(&[0; 8]);
+
+        assert_eq!(
+            RootState::load(&data, &address, Version(3)).map(|root| root.clients_count),
+            Ok(7)
+        );
+
+        match RootState::load_mut(&mut data, &address, Version(3)) {
+            Ok(root) => root.clients_count = 8,
+            Err(err) => panic!("Failed to load root: {}", err),
+        }
+        assert_eq!(
+            RootState::load(&data, &address, Version(3)).map(|root| root.clients_count),
+            Ok(8)
+        );
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let address = Pubkey::new_unique();
+        let root = RootState {
+            discriminator: Discriminator::new(Tag(account_type::ROOT), Version(3)),
+            ..Default::default()
+        };
+        let data = bytemuck::bytes_of(&root);
+
+        assert_eq!(
+            RootState::load(&data[..8], &address, Version(3)).err(),
+            Some(ModelsError::InvalidAccountSize {
+                address,
+                account_type: AccountType::Root,
+                expected: size_of::<RootState>(),
+                actual: 8,
+            })
+        );
+        assert_eq!(
+            RootState::load(data, &address, Version(4)).err(),
+            Some(ModelsError::InvalidVersion {
+                address,
+                account_type: AccountType::Root,
+                expected: 4,
+                actual: 3,
+            })
+        );
+        assert_eq!(
+            TokenState::load(
+                bytemuck::bytes_of(&TokenState::zeroed()),
+                &address,
+                Version(3)
+            )
+            .err()
+            .map(|err| matches!(err, ModelsError::InvalidAccountTag { actual_tag: 0, .. })),
+            Some(true)
+        );
+
+        let mut unaligned = vec![0u8; size_of::<RootState>() + 1];
+        unaligned[1..].copy_from_slice(data);
+        assert_eq!(
+            RootState::load(&unaligned[1..], &address, Version(3)).err(),
+            Some(ModelsError::InvalidDataAlignment { address })
+        );
+    }
+
+    #[test]
+    fn test_generic_account_type() {
+        assert_eq!(
+            <SpotTradeAccountHeader<{ account_type::SPOT_MAPS }>>::ACCOUNT_TYPE,
+            AccountType::SpotMaps
+        );
+    }
+}
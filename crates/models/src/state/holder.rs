@@ -2,6 +2,7 @@ use std::mem::size_of;
 
 use bytemuck::{Pod, Zeroable};
 
+#[drv_macros::drv_layout(size = 8, align = 4)]
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default, Zeroable, Pod)]
 pub struct HolderAccountHeader {
@@ -0,0 +1,489 @@
+//! # Instrument invariants module
+//! - Cross-checks `InstrAccountHeader` against the trade accounts of one instrument
+//! - Every violation is reported, checks depending on an unreadable account
+//!   are skipped
+//! - Spot clients lock crncy in bids (`Order.sum`) and asset in asks (`Order.qty`)
+
+use solana_pubkey::Pubkey;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    mem::size_of,
+};
+
+use crate::{
+    errors::ModelsError,
+    new_types::{client::ClientId, instrument::InstrId},
+    state::{
+        instrument::InstrAccountHeader,
+        order_book::{walk_side, OrderBookKind, SideCounters},
+        perps::perp_trade_header::PerpTradeAccountHeader,
+        records::records,
+        spots::{
+            spot_account_header::SpotTradeAccountHeader,
+            spot_infos::{SpotClientInfo2, SpotClientInfos},
+        },
+        types::{Order, OrderSide, PxOrders},
+    },
+};
+
+/// Instrument Accounts
+///
+/// Accounts data with addresses, `spot_client_infos2` is the
+/// `SpotClientInfo2` account of a `OrderBookKind::Spot` instrument. Perp
+/// clients lock funds depending on leverage, their locked amounts are not
+/// checked.
+pub struct InstrAccounts<'a> {
+    pub instr: &'a InstrAccountHeader,
+    pub kind: OrderBookKind,
+    pub bid_orders: (&'a [u8], Pubkey),
+    pub ask_orders: (&'a [u8], Pubkey),
+    pub lines: (&'a [u8], Pubkey),
+    pub spot_client_infos2: Option<(&'a [u8], Pubkey)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InvariantViolation {
+    /// Account or its records can not be read
+    UnreadableAccount { address: Pubkey, error: ModelsError },
+    /// Account is not expected for the instrument kind, it is not checked
+    UnexpectedAccount {
+        address: Pubkey,
+        kind: OrderBookKind,
+    },
+    InstrIdMismatch {
+        address: Pubkey,
+        expected: InstrId,
+        actual: InstrId,
+    },
+    /// Lines or orders list is broken
+    BrokenList { side: OrderSide, error: ModelsError },
+    LinesCount {
+        side: OrderSide,
+        expected: u32,
+        actual: u32,
+    },
+    OrdersCount {
+        side: OrderSide,
+        expected: u32,
+        actual: u32,
+    },
+    /// `expected` is the line qty, `actual` is the sum of its orders qty
+    LineQty {
+        side: OrderSide,
+        line: u32,
+        expected: i64,
+        actual: i64,
+    },
+    /// `expected` is the header best price, `actual` is the first line price
+    BestPrice {
+        side: OrderSide,
+        expected: i64,
+        actual: i64,
+    },
+    InOrdersAssetTokens {
+        client: ClientId,
+        expected: i64,
+        actual: i64,
+    },
+    InOrdersCrncyTokens {
+        client: ClientId,
+        expected: i64,
+        actual: i64,
+    },
+}
+
+const fn best_price(instr: &InstrAccountHeader, kind: OrderBookKind, side: OrderSide) -> i64 {
+    match (kind, side) {
+        (OrderBookKind::Spot, OrderSide::Bid) => instr.best_bid,
+        (OrderBookKind::Spot, OrderSide::Ask) => instr.best_ask,
+        (OrderBookKind::Perp, OrderSide::Bid) => instr.perp_best_bid,
+        (OrderBookKind::Perp, OrderSide::Ask) => instr.perp_best_ask,
+    }
+}
+
+fn trade_account_instr_id(kind: OrderBookKind, data: &[u8]) -> Option<InstrId> {
+    match kind {
+        OrderBookKind::Spot => data
+            .get(..size_of::<SpotTradeAccountHeader<0>>())
+            .and_then(|data| {
+                bytemuck::try_pod_read_unaligned::<SpotTradeAccountHeader<0>>(data).ok()
+            })
+            .map(|header| header.instr_id),
+        OrderBookKind::Perp => data
+            .get(..size_of::<PerpTradeAccountHeader<0>>())
+            .and_then(|data| {
+                bytemuck::try_pod_read_unaligned::<PerpTradeAccountHeader<0>>(data).ok()
+            })
+            .map(|header| header.id),
+    }
+}
+
+/// Locked tokens of spot clients by temp client id
+#[derive(Default)]
+struct InOrders {
+    asset_tokens: BTreeMap<u32, i64>,
+    crncy_tokens: BTreeMap<u32, i64>,
+}
+
+impl InOrders {
+    fn add(&mut self, side: OrderSide, order: &Order) {
+        let (tokens, amount) = match side {
+            OrderSide::Bid => (&mut self.crncy_tokens, order.sum),
+            OrderSide::Ask => (&mut self.asset_tokens, order.qty),
+        };
+
+        *tokens.entry(*order.client_id).or_default() += amount;
+    }
+}
+
+fn verify_side(
+    accounts: &InstrAccounts,
+    side: OrderSide,
+    lines: &[PxOrders],
+    orders: &[Order],
+    in_orders: &mut InOrders,
+    violations: &mut Vec<InvariantViolation>,
+) {
+    let counters = SideCounters::new(accounts.instr, accounts.kind, side);
+
+    let book_lines = match walk_side(counters.lines_begin, side, lines, orders) {
+        Ok(book_lines) => book_lines,
+        Err(error) => {
+            violations.push(InvariantViolation::BrokenList { side, error });
+            return;
+        }
+    };
+
+    if book_lines.len() != counters.lines_count as usize {
+        violations.push(InvariantViolation::LinesCount {
+            side,
+            expected: counters.lines_count,
+            actual: book_lines.len() as u32,
+        });
+    }
+
+    let orders_count = book_lines
+        .iter()
+        .map(|line| line.orders.len())
+        .sum::<usize>();
+    if orders_count != counters.orders_count as usize {
+        violations.push(InvariantViolation::OrdersCount {
+            side,
+            expected: counters.orders_count,
+            actual: orders_count as u32,
+        });
+    }
+
+    if let Some(first) = book_lines.first() {
+        let expected = best_price(accounts.instr, accounts.kind, side);
+
+        if first.line.price != expected {
+            violations.push(InvariantViolation::BestPrice {
+                side,
+                expected,
+                actual: first.line.price,
+            });
+        }
+    }
+
+    for book_line in &book_lines {
+        let qty = book_line.orders.iter().map(|order| order.qty).sum::<i64>();
+
+        if qty != book_line.line.qty {
+            violations.push(InvariantViolation::LineQty {
+                side,
+                line: book_line.line.sref,
+                expected: book_line.line.qty,
+                actual: qty,
+            });
+        }
+
+        book_line
+            .orders
+            .iter()
+            .for_each(|order| in_orders.add(side, order));
+    }
+}
+
+fn verify_in_orders(
+    (data, address): (&[u8], Pubkey),
+    in_orders: &InOrders,
+    violations: &mut Vec<InvariantViolation>,
+) {
+    let infos = SpotClientInfos::<SpotClientInfo2>::new(data, address);
+
+    let iter = match infos.iter() {
+        Ok(iter) => iter,
+        Err(error) => {
+            violations.push(InvariantViolation::UnreadableAccount { address, error });
+            return;
+        }
+    };
+
+    for (client, info) in iter {
+        let asset_tokens = in_orders
+            .asset_tokens
+            .get(&*client)
+            .copied()
+            .unwrap_or_default();
+        if asset_tokens != info.in_orders_asset_tokens {
+            violations.push(InvariantViolation::InOrdersAssetTokens {
+                client,
+                expected: info.in_orders_asset_tokens,
+                actual: asset_tokens,
+            });
+        }
+
+        let crncy_tokens = in_orders
+            .crncy_tokens
+            .get(&*client)
+            .copied()
+            .unwrap_or_default();
+        if crncy_tokens != info.in_orders_crncy_tokens {
+            violations.push(InvariantViolation::InOrdersCrncyTokens {
+                client,
+                expected: info.in_orders_crncy_tokens,
+                actual: crncy_tokens,
+            });
+        }
+    }
+
+    // Orders of clients without a stored info record
+    let clients = in_orders
+        .asset_tokens
+        .keys()
+        .chain(in_orders.crncy_tokens.keys())
+        .collect::<BTreeSet<_>>();
+    for &client in clients {
+        if let Err(error) = infos.get(ClientId(client)) {
+            violations.push(InvariantViolation::UnreadableAccount { address, error });
+        }
+    }
+}
+
+/// Checks instrument header counters, best prices, line quantities, spot
+/// clients locked tokens and instrument ids of trade accounts
+///
+/// Returns all found violations, empty when the account set is consistent
+pub fn verify_instrument_invariants(accounts: &InstrAccounts) -> Vec<InvariantViolation> {
+    let mut violations = Vec::new();
+    let header_size = accounts.kind.header_size();
+
+    let spot_client_infos2 = match (accounts.kind, accounts.spot_client_infos2) {
+        (OrderBookKind::Perp, Some((_, address))) => {
+            violations.push(InvariantViolation::UnexpectedAccount {
+                address,
+                kind: OrderBookKind::Perp,
+            });
+            None
+        }
+        (_, spot_client_infos2) => spot_client_infos2,
+    };
+
+    let trade_accounts = [accounts.bid_orders, accounts.ask_orders, accounts.lines]
+        .into_iter()
+        .chain(spot_client_infos2);
+
+    for (data, address) in trade_accounts {
+        match trade_account_instr_id(accounts.kind, data) {
+            Some(actual) if actual != accounts.instr.instr_id => {
+                violations.push(InvariantViolation::InstrIdMismatch {
+                    address,
+                    expected: accounts.instr.instr_id,
+                    actual,
+                })
+            }
+            Some(_) => {}
+            None => violations.push(InvariantViolation::UnreadableAccount {
+                address,
+                error: ModelsError::InvalidDataLength {
+                    expected: header_size,
+                    actual: data.len(),
+                },
+            }),
+        }
+    }
+
+    let account_records = (
+        records::<PxOrders>(accounts.lines.0, header_size, &accounts.lines.1),
+        records::<Order>(accounts.bid_orders.0, header_size, &accounts.bid_orders.1),
+        records::<Order>(accounts.ask_orders.0, header_size, &accounts.ask_orders.1),
+    );
+
+    let (lines, bid_orders, ask_orders) = match account_records {
+        (Ok(lines), Ok(bid_orders), Ok(ask_orders)) => (lines, bid_orders, ask_orders),
+        (lines, bid_orders, ask_orders) => {
+            let errors = [
+                (accounts.lines.1, lines.err()),
+                (accounts.bid_orders.1, bid_orders.err()),
+                (accounts.ask_orders.1, ask_orders.err()),
+            ];
+            violations.extend(errors.into_iter().filter_map(|(address, error)| {
+                error.map(|error| InvariantViolation::UnreadableAccount { address, error })
+            }));
+
+            return violations;
+        }
+    };
+
+    let mut in_orders = InOrders::default();
+    for (side, orders) in [(OrderSide::Bid, bid_orders), (OrderSide::Ask, ask_orders)] {
+        verify_side(
+            accounts,
+            side,
+            lines,
+            orders,
+            &mut in_orders,
+            &mut violations,
+        );
+    }
+
+    if let Some(spot_client_infos2) = spot_client_infos2 {
+        verify_in_orders(spot_client_infos2, &in_orders, &mut violations);
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::nulls::NULL_ORDER,
+        state::{records::test_account, types::Discriminator},
+    };
+    use bytemuck::{Pod, Zeroable};
+
+    fn account<T: Pod>(instr_id: InstrId, records: &[T]) -> Vec<u64> {
+        let header = SpotTradeAccountHeader::<0>::new(Discriminator::zeroed(), instr_id, 0, 0, 0);
+        test_account(bytemuck::bytes_of(&header), records)
+    }
+
+    fn order(qty: i64, sum: i64, client: u32, next: u32) -> Order {
+        Order {
+            qty,
+            sum,
+            client_id: ClientId(client),
+            next,
+            ..Order::zeroed()
+        }
+    }
+
+    fn line(price: i64, qty: i64, sref: u32, begin: u32) -> PxOrders {
+        PxOrders {
+            price,
+            qty,
+            next: NULL_ORDER,
+            prev: NULL_ORDER,
+            sref,
+            link: 0,
+            begin,
+            end: NULL_ORDER,
+        }
+    }
+
+    fn client_info(asset_tokens: i64, crncy_tokens: i64) -> SpotClientInfo2 {
+        SpotClientInfo2 {
+            in_orders_asset_tokens: asset_tokens,
+            in_orders_crncy_tokens: crncy_tokens,
+            ..SpotClientInfo2::zeroed()
+        }
+    }
+
+    fn verify(instr: &InstrAccountHeader, lines_instr_id: InstrId) -> Vec<InvariantViolation> {
+        let lines = account(lines_instr_id, &[line(100, 3, 0, 0), line(110, 4, 1, 0)]);
+        let bid_orders = account(
+            InstrId(1),
+            &[order(1, 100, 0, 1), order(2, 200, 1, NULL_ORDER)],
+        );
+        let ask_orders = account(InstrId(1), &[order(4, 440, 1, NULL_ORDER)]);
+        let client_infos2 = account(InstrId(1), &[client_info(0, 100), client_info(4, 200)]);
+
+        verify_instrument_invariants(&InstrAccounts {
+            instr,
+            kind: OrderBookKind::Spot,
+            bid_orders: (bytemuck::cast_slice(&bid_orders), Pubkey::new_unique()),
+            ask_orders: (bytemuck::cast_slice(&ask_orders), Pubkey::new_unique()),
+            lines: (bytemuck::cast_slice(&lines), Pubkey::new_unique()),
+            spot_client_infos2: Some((bytemuck::cast_slice(&client_infos2), Pubkey::new_unique())),
+        })
+    }
+
+    fn instr_header() -> InstrAccountHeader {
+        InstrAccountHeader {
+            instr_id: InstrId(1),
+            best_bid: 100,
+            best_ask: 110,
+            bid_lines_begin: 0,
+            bid_lines_count: 1,
+            bid_orders_count: 2,
+            ask_lines_begin: 1,
+            ask_lines_count: 1,
+            ask_orders_count: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_consistent_instrument() {
+        assert_eq!(verify(&instr_header(), InstrId(1)), vec![]);
+    }
+
+    #[test]
+    fn test_violations() {
+        let instr = InstrAccountHeader {
+            best_ask: 120,
+            bid_orders_count: 3,
+            ..instr_header()
+        };
+        let violations = verify(&instr, InstrId(2));
+
+        assert!(matches!(
+            violations[0],
+            InvariantViolation::InstrIdMismatch {
+                expected: InstrId(1),
+                actual: InstrId(2),
+                ..
+            }
+        ));
+        assert_eq!(
+            violations[1..],
+            [
+                InvariantViolation::OrdersCount {
+                    side: OrderSide::Bid,
+                    expected: 3,
+                    actual: 2,
+                },
+                InvariantViolation::BestPrice {
+                    side: OrderSide::Ask,
+                    expected: 120,
+                    actual: 110,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_perp_with_spot_client_infos() {
+        let empty = account::<Order>(InstrId(1), &[]);
+        let address = Pubkey::new_unique();
+
+        let violations = verify_instrument_invariants(&InstrAccounts {
+            instr: &instr_header(),
+            kind: OrderBookKind::Perp,
+            bid_orders: (bytemuck::cast_slice(&empty), Pubkey::new_unique()),
+            ask_orders: (bytemuck::cast_slice(&empty), Pubkey::new_unique()),
+            lines: (bytemuck::cast_slice(&empty), Pubkey::new_unique()),
+            spot_client_infos2: Some((bytemuck::cast_slice(&empty), address)),
+        });
+
+        assert_eq!(
+            violations.first(),
+            Some(&InvariantViolation::UnexpectedAccount {
+                address,
+                kind: OrderBookKind::Perp,
+            })
+        );
+    }
+}
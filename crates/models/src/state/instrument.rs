@@ -148,6 +148,7 @@ use solana_pubkey::Pubkey;
 /// 124. **`max_leverage`** - Dynamic value of currently max leverage, based on market volatility. max_leverage <= MAX_PERP_LEVERAGE
 /// 125. **`liquidation_threshold`** - Threshold for liquidation process, based on makret volaitlity. liquidation_threshold <= MIN_LIQUIDATION_THRESHOLD
 /// 126. **`seats_reserve`** - Current amount of funds spent on seats purchasing
+#[drv_macros::drv_layout(size = 1064, align = 8)]
 #[repr(C)]
 #[derive(Pod, Zeroable, Clone, Copy, Default, PartialEq, Debug)]
 pub struct InstrAccountHeader {
@@ -1,11 +1,16 @@
 pub mod candles;
 pub mod client_community;
+pub mod client_infos;
 pub mod client_primary_account_header;
 pub mod community_account_header;
+pub mod drv_account;
 pub mod holder;
+pub mod instr_invariants;
 pub mod instrument;
+pub mod order_book;
 pub mod perps;
 pub mod private_mode;
+pub mod records;
 pub mod root;
 pub mod spots;
 pub mod token;
@@ -0,0 +1,357 @@
+//! # Order book module
+//! - Off-chain reconstruction of spot and perp order books
+//! - Lines of each side form a linked list starting at `*_lines_begin`,
+//!   orders of each line form a linked list starting at `PxOrders.begin`
+//! - Lines and orders records follow the trade account header and are
+//!   indexed by their memory map index
+
+use solana_pubkey::Pubkey;
+
+use crate::{
+    constants::nulls::{NULL_NODE, NULL_ORDER},
+    errors::ModelsError,
+    state::{
+        instrument::InstrAccountHeader,
+        perps::perp_trade_header::PERP_TRADE_ACCOUNT_HEADER_SIZE,
+        records::records,
+        spots::spot_account_header::SPOT_TRADE_ACCOUNT_HEADER_SIZE,
+        types::{LineQuotes, Order, OrderSide, PxOrders},
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderBookKind {
+    Spot,
+    Perp,
+}
+
+impl OrderBookKind {
+    pub(crate) const fn header_size(self) -> usize {
+        match self {
+            Self::Spot => SPOT_TRADE_ACCOUNT_HEADER_SIZE,
+            Self::Perp => PERP_TRADE_ACCOUNT_HEADER_SIZE,
+        }
+    }
+}
+
+/// Order Book Side Counters
+///
+/// 1. **`lines_begin`** - Head of lines linked list
+/// 2. **`lines_count`** - Total amount of lines
+/// 3. **`orders_count`** - Total amount of orders
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SideCounters {
+    pub lines_begin: u32,
+    pub lines_count: u32,
+    pub orders_count: u32,
+}
+
+impl SideCounters {
+    /// Counters of `side` stored in the instrument header
+    pub const fn new(instr: &InstrAccountHeader, kind: OrderBookKind, side: OrderSide) -> Self {
+        match (kind, side) {
+            (OrderBookKind::Spot, OrderSide::Bid) => Self {
+                lines_begin: instr.bid_lines_begin,
+                lines_count: instr.bid_lines_count,
+                orders_count: instr.bid_orders_count,
+            },
+            (OrderBookKind::Spot, OrderSide::Ask) => Self {
+                lines_begin: instr.ask_lines_begin,
+                lines_count: instr.ask_lines_count,
+                orders_count: instr.ask_orders_count,
+            },
+            (OrderBookKind::Perp, OrderSide::Bid) => Self {
+                lines_begin: instr.perp_bid_lines_begin,
+                lines_count: instr.perp_bid_lines_count,
+                orders_count: instr.perp_bid_orders_count,
+            },
+            (OrderBookKind::Perp, OrderSide::Ask) => Self {
+                lines_begin: instr.perp_ask_lines_begin,
+                lines_count: instr.perp_ask_lines_count,
+                orders_count: instr.perp_ask_orders_count,
+            },
+        }
+    }
+}
+
+/// Book Line
+///
+/// Price line with its orders in queue priority
+#[derive(Clone, Debug, PartialEq)]
+pub struct BookLine {
+    pub line: PxOrders,
+    pub orders: Vec<Order>,
+}
+
+/// Order Book
+///
+/// Lines of both sides in list order, best price first
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OrderBook {
+    pub bids: Vec<BookLine>,
+    pub asks: Vec<BookLine>,
+}
+
+impl OrderBook {
+    /// Walks lines and orders lists of both sides
+    ///
+    /// Accounts are passed as data with address, both sides share one lines account
+    ///
+    /// ### Errors
+    /// - `InvalidDataAlignment` - account data is not aligned
+    /// - `InvalidBidLinesCount`, `InvalidAskLinesCount` - line is not stored in
+    ///   lines account or walked lines do not match header counter
+    /// - `OrderWasNotFound` - order is not stored in orders account
+    /// - `InvalidBidOrdersCount`, `InvalidAskOrdersCount` - walked orders do not
+    ///   match header counter
+    pub fn from_accounts(
+        instr: &InstrAccountHeader,
+        kind: OrderBookKind,
+        bid_orders: (&[u8], Pubkey),
+        ask_orders: (&[u8], Pubkey),
+        lines: (&[u8], Pubkey),
+    ) -> Result<Self, ModelsError> {
+        let lines = records::<PxOrders>(lines.0, kind.header_size(), &lines.1)?;
+
+        Ok(Self {
+            bids: checked_side(
+                SideCounters::new(instr, kind, OrderSide::Bid),
+                OrderSide::Bid,
+                lines,
+                records(bid_orders.0, kind.header_size(), &bid_orders.1)?,
+            )?,
+            asks: checked_side(
+                SideCounters::new(instr, kind, OrderSide::Ask),
+                OrderSide::Ask,
+                lines,
+                records(ask_orders.0, kind.header_size(), &ask_orders.1)?,
+            )?,
+        })
+    }
+
+    pub fn side(&self, side: OrderSide) -> &[BookLine] {
+        match side {
+            OrderSide::Bid => &self.bids,
+            OrderSide::Ask => &self.asks,
+        }
+    }
+
+    /// Level 2 book, aggregated quantity per price line
+    pub fn level2(&self, side: OrderSide) -> impl Iterator<Item = LineQuotes> + '_ {
+        self.side(side).iter().map(|line| LineQuotes {
+            px: line.line.price,
+            qty: line.line.qty,
+        })
+    }
+
+    /// Level 3 book, individual orders in price and queue priority
+    pub fn level3(&self, side: OrderSide) -> impl Iterator<Item = &Order> + '_ {
+        self.side(side).iter().flat_map(|line| line.orders.iter())
+    }
+}
+
+const fn is_null(index: u32) -> bool {
+    index == NULL_ORDER || index == NULL_NODE
+}
+
+const fn count_errors(side: OrderSide) -> (ModelsError, ModelsError) {
+    match side {
+        OrderSide::Bid => (
+            ModelsError::InvalidBidLinesCount,
+            ModelsError::InvalidBidOrdersCount,
+        ),
+        OrderSide::Ask => (
+            ModelsError::InvalidAskLinesCount,
+            ModelsError::InvalidAskOrdersCount,
+        ),
+    }
+}
+
+/// Walks lines list starting at `lines_begin` with orders of each line
+///
+/// Stored records bound every walk, so a looped list fails instead of hanging
+pub(crate) fn walk_side(
+    lines_begin: u32,
+    side: OrderSide,
+    lines: &[PxOrders],
+    orders: &[Order],
+) -> Result<Vec<BookLine>, ModelsError> {
+    let (lines_count_error, orders_count_error) = count_errors(side);
+
+    let mut book_lines = Vec::new();
+    let mut orders_count = 0;
+    let mut line_index = lines_begin;
+
+    while !is_null(line_index) {
+        if book_lines.len() == lines.len() {
+            return Err(lines_count_error);
+        }
+        let line = *lines.get(line_index as usize).ok_or(lines_count_error)?;
+
+        let mut line_orders = Vec::new();
+        let mut order_index = line.begin;
+
+        while !is_null(order_index) {
+            if orders_count == orders.len() {
+                return Err(orders_count_error);
+            }
+            let order = *orders
+                .get(order_index as usize)
+                .ok_or(ModelsError::OrderWasNotFound {
+                    link: order_index,
+                    side,
+                })?;
+
+            orders_count += 1;
+            order_index = order.next;
+            line_orders.push(order);
+        }
+
+        line_index = line.next;
+        book_lines.push(BookLine {
+            line,
+            orders: line_orders,
+        });
+    }
+
+    Ok(book_lines)
+}
+
+fn checked_side(
+    counters: SideCounters,
+    side: OrderSide,
+    lines: &[PxOrders],
+    orders: &[Order],
+) -> Result<Vec<BookLine>, ModelsError> {
+    let (lines_count_error, orders_count_error) = count_errors(side);
+    let book_lines = walk_side(counters.lines_begin, side, lines, orders)?;
+
+    if book_lines.len() != counters.lines_count as usize {
+        return Err(lines_count_error);
+    }
+    if book_lines
+        .iter()
+        .map(|line| line.orders.len())
+        .sum::<usize>()
+        != counters.orders_count as usize
+    {
+        return Err(orders_count_error);
+    }
+
+    Ok(book_lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{new_types::client::ClientId, state::records::test_account};
+    use bytemuck::{Pod, Zeroable};
+
+    fn account<T: Pod>(records: &[T]) -> Vec<u64> {
+        test_account(&[0; SPOT_TRADE_ACCOUNT_HEADER_SIZE], records)
+    }
+
+    fn line(price: i64, qty: i64, next: u32, begin: u32) -> PxOrders {
+        PxOrders {
+            price,
+            qty,
+            next,
+            prev: NULL_ORDER,
+            sref: 0,
+            link: 0,
+            begin,
+            end: NULL_ORDER,
+        }
+    }
+
+    fn order(qty: i64, client: u32, next: u32) -> Order {
+        Order {
+            qty,
+            client_id: ClientId(client),
+            next,
+            ..Order::zeroed()
+        }
+    }
+
+    fn instr_header() -> InstrAccountHeader {
+        InstrAccountHeader {
+            bid_lines_begin: 1,
+            bid_lines_count: 2,
+            bid_orders_count: 3,
+            ask_lines_begin: 2,
+            ask_lines_count: 1,
+            ask_orders_count: 1,
+            ..Default::default()
+        }
+    }
+
+    fn book(instr: &InstrAccountHeader) -> Result<OrderBook, ModelsError> {
+        let lines = account(&[
+            line(90, 5, NULL_ORDER, 2),
+            line(100, 3, 0, 0),
+            line(110, 7, NULL_ORDER, 0),
+        ]);
+        let bid_orders = account(&[
+            order(1, 1, 1),
+            order(2, 2, NULL_ORDER),
+            order(5, 3, NULL_ORDER),
+        ]);
+        let ask_orders = account(&[order(7, 4, NULL_ORDER)]);
+
+        OrderBook::from_accounts(
+            instr,
+            OrderBookKind::Spot,
+            (bytemuck::cast_slice(&bid_orders), Pubkey::new_unique()),
+            (bytemuck::cast_slice(&ask_orders), Pubkey::new_unique()),
+            (bytemuck::cast_slice(&lines), Pubkey::new_unique()),
+        )
+    }
+
+    #[test]
+    fn test_order_book() {
+        let book = match book(&instr_header()) {
+            Ok(book) => book,
+            Err(err) => panic!("Failed to read order book: {}", err),
+        };
+
+        assert_eq!(
+            book.level2(OrderSide::Bid)
+                .map(|line| (line.px, line.qty))
+                .collect::<Vec<_>>(),
+            vec![(100, 3), (90, 5)]
+        );
+        assert_eq!(
+            book.level3(OrderSide::Bid)
+                .map(|order| (*order.client_id, order.qty))
+                .collect::<Vec<_>>(),
+            vec![(1, 1), (2, 2), (3, 5)]
+        );
+        assert_eq!(
+            book.level2(OrderSide::Ask)
+                .map(|line| (line.px, line.qty))
+                .collect::<Vec<_>>(),
+            vec![(110, 7)]
+        );
+    }
+
+    #[test]
+    fn test_order_book_counters() {
+        let instr = InstrAccountHeader {
+            bid_lines_count: 1,
+            ..instr_header()
+        };
+        assert_eq!(book(&instr), Err(ModelsError::InvalidBidLinesCount));
+
+        let instr = InstrAccountHeader {
+            ask_orders_count: 2,
+            ..instr_header()
+        };
+        assert_eq!(book(&instr), Err(ModelsError::InvalidAskOrdersCount));
+
+        let instr = InstrAccountHeader {
+            ask_lines_begin: 3,
+            ..instr_header()
+        };
+        assert_eq!(book(&instr), Err(ModelsError::InvalidAskLinesCount));
+    }
+}
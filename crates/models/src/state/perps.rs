@@ -1,2 +1,3 @@
 pub mod perp_infos;
+pub mod perp_position;
 pub mod perp_trade_header;
@@ -7,7 +7,15 @@ use bytemuck::{Pod, Zeroable};
 use std::mem::size_of;
 
 use super::perp_trade_header::PERP_TRADE_ACCOUNT_HEADER_SIZE;
+use crate::state::client_infos::{ClientInfos, ClientInfosMut};
 
+/// Checked view over perp client info records
+pub type PerpClientInfos<'a, T> = ClientInfos<'a, T, PERP_TRADE_ACCOUNT_HEADER_SIZE>;
+
+/// Checked mutable view over perp client info records
+pub type PerpClientInfosMut<'a, T> = ClientInfosMut<'a, T, PERP_TRADE_ACCOUNT_HEADER_SIZE>;
+
+/// Unchecked pointer to a client info record, prefer `PerpClientInfos`
 pub fn get_perp_info<T>(data: &[u8], id: ClientId) -> *mut T {
     data[PERP_TRADE_ACCOUNT_HEADER_SIZE + size_of::<T>() * *id as usize..].as_ptr() as *mut T
 }
@@ -0,0 +1,286 @@
+//! # Perp position module
+//! - Combines `PerpClientInfo` .. `PerpClientInfo5` stored at the same temp client id
+//! - Exposes values derived from the position, prices follow the instrument
+//!   convention `crncy = perps * px / dec_factor`
+
+use solana_pubkey::Pubkey;
+
+use crate::{
+    errors::ModelsError,
+    new_types::client::ClientId,
+    state::{
+        instrument::InstrAccountHeader,
+        perps::perp_infos::{
+            PerpClientInfo, PerpClientInfo2, PerpClientInfo3, PerpClientInfo4, PerpClientInfo5,
+            PerpClientInfos,
+        },
+    },
+};
+
+/// Position side bit of `PerpClientInfo2.mask`
+pub const POSITION_SIDE_BIT: u32 = 1 << 31;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PositionSide {
+    Long,
+    Short,
+}
+
+impl PositionSide {
+    const fn sign(self) -> f64 {
+        match self {
+            Self::Long => 1.0,
+            Self::Short => -1.0,
+        }
+    }
+}
+
+/// Perp Position
+///
+/// Client perp state combined from five client info accounts
+#[derive(Clone, Copy, Debug)]
+pub struct PerpPosition {
+    pub info: PerpClientInfo,
+    pub info2: PerpClientInfo2,
+    pub info3: PerpClientInfo3,
+    pub info4: PerpClientInfo4,
+    pub info5: PerpClientInfo5,
+}
+
+impl PerpPosition {
+    pub const fn new(
+        info: PerpClientInfo,
+        info2: PerpClientInfo2,
+        info3: PerpClientInfo3,
+        info4: PerpClientInfo4,
+        info5: PerpClientInfo5,
+    ) -> Self {
+        Self {
+            info,
+            info2,
+            info3,
+            info4,
+            info5,
+        }
+    }
+
+    /// Reads records of `id` from all five client info accounts
+    ///
+    /// ### Errors
+    /// - `OffsetOutOfBounds` - client is not stored in one of accounts
+    /// - `InvalidDataAlignment` - account data is not aligned
+    pub fn from_infos(
+        id: ClientId,
+        infos: &PerpClientInfos<PerpClientInfo>,
+        infos2: &PerpClientInfos<PerpClientInfo2>,
+        infos3: &PerpClientInfos<PerpClientInfo3>,
+        infos4: &PerpClientInfos<PerpClientInfo4>,
+        infos5: &PerpClientInfos<PerpClientInfo5>,
+    ) -> Result<Self, ModelsError> {
+        Ok(Self::new(
+            *infos.get(id)?,
+            *infos2.get(id)?,
+            *infos3.get(id)?,
+            *infos4.get(id)?,
+            *infos5.get(id)?,
+        ))
+    }
+
+    /// Original client id
+    pub const fn client(&self) -> ClientId {
+        self.info3.client
+    }
+
+    pub const fn is_open(&self) -> bool {
+        self.info.perps != 0
+    }
+
+    /// Position side decoded from the MSB of `PerpClientInfo2.mask`
+    pub const fn side(&self) -> PositionSide {
+        if self.info2.mask & POSITION_SIDE_BIT == 0 {
+            PositionSide::Long
+        } else {
+            PositionSide::Short
+        }
+    }
+
+    /// Current leverage decoded from `PerpClientInfo2.mask`
+    pub fn leverage(&self) -> i64 {
+        self.info2.leverage()
+    }
+
+    /// Amount of perps in position, positive for long and negative for short
+    pub fn signed_perps(&self) -> f64 {
+        self.side().sign() * self.info.perps.unsigned_abs() as f64
+    }
+
+    /// Position cost, positive when funds were spent and negative when received
+    pub fn signed_cost(&self) -> f64 {
+        self.side().sign() * self.info2.cost.unsigned_abs() as f64
+    }
+
+    /// Average entry price `cost / perps`, `None` for a closed position
+    pub fn entry_price(&self, instr: &InstrAccountHeader) -> Option<f64> {
+        self.is_open().then(|| {
+            self.info2.cost.unsigned_abs() as f64 * instr.dec_factor as f64
+                / self.info.perps.unsigned_abs() as f64
+        })
+    }
+
+    /// Position value in crncy at `mark_px`
+    pub fn notional(&self, instr: &InstrAccountHeader, mark_px: i64) -> f64 {
+        self.info.perps.unsigned_abs() as f64 * mark_px as f64 / instr.dec_factor as f64
+    }
+
+    /// Unrealized profit or loss in crncy at `mark_px`
+    pub fn unrealized_pnl(&self, instr: &InstrAccountHeader, mark_px: i64) -> f64 {
+        self.signed_perps() * mark_px as f64 / instr.dec_factor as f64 - self.signed_cost()
+    }
+
+    /// Client funds with unrealized profit or loss at `mark_px`
+    pub fn equity(&self, instr: &InstrAccountHeader, mark_px: i64) -> f64 {
+        self.info.funds as f64 + self.unrealized_pnl(instr, mark_px)
+    }
+
+    /// `equity / notional`, `None` for a closed position
+    pub fn margin_ratio(&self, instr: &InstrAccountHeader, mark_px: i64) -> Option<f64> {
+        let notional = self.notional(instr, mark_px);
+
+        (notional > 0.0).then(|| self.equity(instr, mark_px) / notional)
+    }
+
+    /// Margin ratio above `InstrAccountHeader.liquidation_threshold`,
+    /// position is liquidatable once it is not positive
+    pub fn distance_to_liquidation(&self, instr: &InstrAccountHeader, mark_px: i64) -> Option<f64> {
+        self.margin_ratio(instr, mark_px)
+            .map(|ratio| ratio - instr.liquidation_threshold)
+    }
+
+    /// Mark price at which margin ratio reaches `liquidation_threshold`
+    pub fn liquidation_price(&self, instr: &InstrAccountHeader) -> Option<f64> {
+        if !self.is_open() {
+            return None;
+        }
+
+        let perps = self.info.perps.unsigned_abs() as f64;
+        let cost = self.info2.cost.unsigned_abs() as f64;
+        let funds = self.info.funds as f64;
+        let sign = self.side().sign();
+
+        // funds + sign * (perps * px / dec_factor - cost) = threshold * perps * px / dec_factor
+        let px = sign.mul_add(cost, -funds) * instr.dec_factor as f64
+            / (perps * (sign - instr.liquidation_threshold));
+
+        (px > 0.0).then_some(px)
+    }
+
+    /// Funding funds received from the protocol
+    pub const fn accrued_funding(&self) -> i64 {
+        self.info5.funding_funds
+    }
+
+    /// Funds compensated by socialized loss procedure
+    pub const fn socialized_loss(&self) -> i64 {
+        self.info4.soc_loss_funds
+    }
+}
+
+/// Perp client info accounts of an instrument
+pub struct PerpPositionAccounts<'a> {
+    pub infos: PerpClientInfos<'a, PerpClientInfo>,
+    pub infos2: PerpClientInfos<'a, PerpClientInfo2>,
+    pub infos3: PerpClientInfos<'a, PerpClientInfo3>,
+    pub infos4: PerpClientInfos<'a, PerpClientInfo4>,
+    pub infos5: PerpClientInfos<'a, PerpClientInfo5>,
+}
+
+impl<'a> PerpPositionAccounts<'a> {
+    /// Accounts data with addresses in `PerpClientInfo` .. `PerpClientInfo5` order
+    pub const fn new(accounts: [(&'a [u8], Pubkey); 5]) -> Self {
+        let [infos, infos2, infos3, infos4, infos5] = accounts;
+
+        Self {
+            infos: PerpClientInfos::new(infos.0, infos.1),
+            infos2: PerpClientInfos::new(infos2.0, infos2.1),
+            infos3: PerpClientInfos::new(infos3.0, infos3.1),
+            infos4: PerpClientInfos::new(infos4.0, infos4.1),
+            infos5: PerpClientInfos::new(infos5.0, infos5.1),
+        }
+    }
+
+    pub fn position(&self, id: ClientId) -> Result<PerpPosition, ModelsError> {
+        PerpPosition::from_infos(
+            id,
+            &self.infos,
+            &self.infos2,
+            &self.infos3,
+            &self.infos4,
+            &self.infos5,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+
+    fn position(side: PositionSide) -> PerpPosition {
+        let mut position = PerpPosition::new(
+            PerpClientInfo::zeroed(),
+            PerpClientInfo2::zeroed(),
+            PerpClientInfo3::zeroed(),
+            PerpClientInfo4::zeroed(),
+            PerpClientInfo5::zeroed(),
+        );
+
+        // 10 perps for 1_000 crncy with 1_000 funds
+        position.info.funds = 1_000;
+        position.info.perps = 10;
+        position.info2.cost = 1_000;
+        position.info2.mask = 5;
+        if side == PositionSide::Short {
+            position.info2.mask |= POSITION_SIDE_BIT;
+        }
+
+        position
+    }
+
+    fn instr() -> InstrAccountHeader {
+        InstrAccountHeader {
+            dec_factor: 1,
+            liquidation_threshold: 0.05,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_long_position() {
+        let position = position(PositionSide::Long);
+        let instr = instr();
+
+        assert_eq!(position.side(), PositionSide::Long);
+        assert_eq!(position.leverage(), 5);
+        assert_eq!(position.entry_price(&instr), Some(100.0));
+        assert_eq!(position.unrealized_pnl(&instr, 110), 100.0);
+        assert_eq!(position.margin_ratio(&instr, 100), Some(1.0));
+        assert_eq!(position.distance_to_liquidation(&instr, 100), Some(0.95));
+        assert_eq!(position.liquidation_price(&instr), None);
+    }
+
+    #[test]
+    fn test_short_position() {
+        let position = position(PositionSide::Short);
+        let instr = instr();
+
+        assert_eq!(position.side(), PositionSide::Short);
+        assert_eq!(position.unrealized_pnl(&instr, 110), -100.0);
+
+        let liquidation_price = position.liquidation_price(&instr);
+        assert!(matches!(liquidation_price, Some(px) if px > 110.0));
+        assert!(matches!(
+            liquidation_price.and_then(|px| position.distance_to_liquidation(&instr, px as i64)),
+            Some(distance) if distance.abs() < 0.01
+        ));
+    }
+}
@@ -5,7 +5,7 @@ use std::mem::size_of;
 
 use crate::{new_types::instrument::InstrId, state::types::Discriminator};
 
-#[pod_wrapper]
+#[pod_wrapper(size = 24, align = 4)]
 #[repr(C)]
 #[derive(Pod, Zeroable, Clone, Copy)]
 pub struct PerpTradeAccountHeader<const TAG: u32> {
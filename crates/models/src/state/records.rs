@@ -0,0 +1,41 @@
+//! # Records module
+//! - Zero-copy casts of fixed size records stored after an account header
+//! - Cast failures are reported with the address of the account
+
+use bytemuck::{Pod, PodCastError};
+use solana_pubkey::Pubkey;
+
+use std::mem::size_of;
+
+use crate::errors::ModelsError;
+
+pub(crate) const fn cast_error(err: PodCastError, address: &Pubkey) -> ModelsError {
+    match err {
+        PodCastError::TargetAlignmentGreaterAndInputNotAligned => {
+            ModelsError::InvalidDataAlignment { address: *address }
+        }
+        _ => ModelsError::InvalidClientDataFormat,
+    }
+}
+
+/// `T` records stored after a `header_size` header, a trailing partial record is ignored
+pub(crate) fn records<'a, T: Pod>(
+    data: &'a [u8],
+    header_size: usize,
+    address: &Pubkey,
+) -> Result<&'a [T], ModelsError> {
+    let len = data.len().saturating_sub(header_size) / size_of::<T>() * size_of::<T>();
+
+    bytemuck::try_cast_slice(data.get(header_size..header_size + len).unwrap_or_default())
+        .map_err(|err| cast_error(err, address))
+}
+
+/// Account data for tests, `header` bytes followed by `records`, kept in
+/// `u64` words so that records are aligned
+#[cfg(test)]
+pub(crate) fn test_account<T: Pod>(header: &[u8], records: &[T]) -> Vec<u64> {
+    let mut data = vec![0u64; header.len().div_ceil(8)];
+    bytemuck::cast_slice_mut::<u64, u8>(&mut data)[..header.len()].copy_from_slice(header);
+    data.extend_from_slice(bytemuck::cast_slice(records));
+    data
+}
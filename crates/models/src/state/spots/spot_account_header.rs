@@ -5,7 +5,7 @@ use drv_macros::pod_wrapper;
 use crate::state::types::Discriminator;
 use std::mem::size_of;
 
-#[pod_wrapper]
+#[pod_wrapper(size = 24, align = 4)]
 #[repr(C)]
 #[derive(Pod, Zeroable, Clone, Copy)]
 pub struct SpotTradeAccountHeader<const TAG: u32> {
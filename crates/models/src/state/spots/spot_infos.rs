@@ -7,7 +7,15 @@ use std::mem::size_of;
 use crate::new_types::client::ClientId;
 
 use super::spot_account_header::SPOT_TRADE_ACCOUNT_HEADER_SIZE;
+use crate::state::client_infos::{ClientInfos, ClientInfosMut};
 
+/// Checked view over spot client info records
+pub type SpotClientInfos<'a, T> = ClientInfos<'a, T, SPOT_TRADE_ACCOUNT_HEADER_SIZE>;
+
+/// Checked mutable view over spot client info records
+pub type SpotClientInfosMut<'a, T> = ClientInfosMut<'a, T, SPOT_TRADE_ACCOUNT_HEADER_SIZE>;
+
+/// Unchecked pointer to a client info record, prefer `SpotClientInfos`
 pub fn get_spot_info<T>(data: &[u8], id: ClientId) -> *mut T {
     data[SPOT_TRADE_ACCOUNT_HEADER_SIZE + size_of::<T>() * *id as usize..].as_ptr() as *mut T
 }
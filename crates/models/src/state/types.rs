@@ -2,7 +2,10 @@ use bytemuck::{Pod, Zeroable};
 use serde::{Deserialize, Serialize};
 use solana_pubkey::Pubkey;
 
-use crate::new_types::{client::ClientId, tag::Tag, version::Version};
+use crate::{
+    errors::ModelsError,
+    new_types::{client::ClientId, tag::Tag, version::Version},
+};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum OrderSide {
@@ -23,6 +26,17 @@ impl std::fmt::Display for OrderSide {
     }
 }
 
+impl TryFrom<u8> for OrderSide {
+    type Error = ModelsError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Bid),
+            1 => Ok(Self::Ask),
+            _ => Err(ModelsError::InvalidClientDataFormat),
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
 pub enum MarketSeatOrderType {
     Buy,
@@ -66,6 +80,21 @@ impl std::fmt::Display for OrderType {
     }
 }
 
+impl TryFrom<u8> for OrderType {
+    type Error = ModelsError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Limit),
+            1 => Ok(Self::Market),
+            2 => Ok(Self::MarginCall),
+            3 => Ok(Self::MakerOnly),
+            _ => Err(ModelsError::UnknownOrderType {
+                order_type_raw: value,
+            }),
+        }
+    }
+}
+
 pub mod root_mask {
     pub const PRIVATE_MODE: u32 = 0x1;
 }
@@ -125,6 +154,7 @@ pub mod instr_mask {
 
     #[derive(Clone, Copy, Pod, Zeroable, Debug, Default, PartialEq, Eq)]
     #[repr(transparent)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     pub struct InstrInputMask(u8);
 
     impl InstrInputMask {
@@ -328,10 +358,10 @@ pub mod account_type {
         DrvsAuthority,
     }
 
-    impl TryFrom<u32> for AccountType {
-        type Error = ProgramError;
-        fn try_from(value: u32) -> Result<Self, Self::Error> {
-            Ok(match value {
+    impl AccountType {
+        /// Account type stored under the discriminator `tag`
+        pub const fn from_tag(tag: u32) -> Option<Self> {
+            Some(match tag {
                 1 => Self::Holder,
                 2 => Self::Root,
                 4 => Self::Token,
@@ -368,22 +398,51 @@ pub mod account_type {
                 50 => Self::PerpRebalanceTimeTree,
                 51 => Self::PrivateClients,
 
-                _ => return Err(ProgramError::InvalidAccountData),
+                _ => return None,
             })
         }
     }
 
+    impl TryFrom<u32> for AccountType {
+        type Error = ProgramError;
+        fn try_from(value: u32) -> Result<Self, Self::Error> {
+            Self::from_tag(value).ok_or(ProgramError::InvalidAccountData)
+        }
+    }
+
     impl std::fmt::Display for AccountType {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             write!(f, "{:?}({})", self, (*self) as u32)
         }
     }
 
+    /// Parses `Display` output, `Community(34)`
+    impl std::str::FromStr for AccountType {
+        type Err = ProgramError;
+
+        fn from_str(value: &str) -> Result<Self, Self::Err> {
+            value
+                .strip_suffix(')')
+                .and_then(|value| value.split_once('('))
+                .and_then(|(name, tag)| {
+                    Self::from_tag(tag.parse().ok()?)
+                        .filter(|account_type| format!("{:?}", account_type) == name)
+                })
+                .ok_or(ProgramError::InvalidAccountData)
+        }
+    }
+
     #[test]
     fn some_test() {
         let account_type = AccountType::Community;
         assert_eq!(format!("{}", account_type), "Community(34)".to_string());
     }
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!("Community(34)".parse(), Ok(AccountType::Community));
+        assert!("Community(35)".parse::<AccountType>().is_err());
+    }
 }
 
 #[repr(C)]
@@ -412,7 +471,7 @@ pub struct Operator {
 pub const OPERATOR_SIZE: usize = std::mem::size_of::<Operator>();
 
 #[repr(C)]
-#[derive(Copy, Clone, Zeroable)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Zeroable)]
 /// Line Quotes
 ///
 /// 1. **`px`** - price
@@ -582,6 +641,7 @@ pub mod vm_status {
 
     #[derive(Clone, Copy, Pod, Zeroable)]
     #[repr(transparent)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     pub struct VmMask(u32);
 
     impl VmMask {
@@ -775,6 +835,7 @@ pub mod quote_status {
     /// - Next 12 bits (4-15): array of booleans where bid = 1 and ask = 0
     #[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
     #[repr(transparent)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     pub struct QuoteMask(pub u16);
 
     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -941,9 +1002,20 @@ pub mod quote_status {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(C)]
 pub enum SAMFeeType {
     ZeroFees = 0,
     FixedFees = 1,
 }
+
+impl TryFrom<u8> for SAMFeeType {
+    type Error = ModelsError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::ZeroFees),
+            1 => Ok(Self::FixedFees),
+            _ => Err(ModelsError::InvalidClientDataFormat),
+        }
+    }
+}